@@ -148,4 +148,238 @@ pub enum RiyalError {
     
     #[msg("Transfers must be permanently enabled to unfreeze accounts")]
     TransfersNotPermanentlyEnabled,
-}
\ No newline at end of file
+    
+    #[msg("Oracle price is stale")]
+    StaleOracle,
+    
+    #[msg("Invalid price oracle account")]
+    InvalidOracleAccount,
+    
+    #[msg("Invalid oracle price")]
+    InvalidOraclePrice,
+    
+    #[msg("Oracle price confidence interval is too wide")]
+    OracleConfidenceTooWide,
+    
+    #[msg("Lifetime supply counter overflow")]
+    SupplyCounterOverflow,
+    
+    #[msg("Maximum number of managed mints reached")]
+    TooManyManagedMints,
+    
+    #[msg("Region is blocked from claims and transfers")]
+    RegionBlocked,
+    
+    #[msg("Invalid region tag")]
+    InvalidRegion,
+    
+    #[msg("Mint would consume supply reserved for the treasury")]
+    ReserveViolation,
+    
+    #[msg("Mint would exceed the maximum supply")]
+    MaxSupplyExceeded,
+    
+    #[msg("Invalid supply cap configuration")]
+    InvalidSupplyCap,
+    
+    #[msg("Token metadata is locked and cannot be changed")]
+    MetadataLocked,
+    
+    #[msg("Too many accounts passed for a single call")]
+    TooManyAccounts,
+    
+    #[msg("Admin config changed too recently - wait for the config-change cooldown")]
+    ConfigChangeTooSoon,
+    
+    #[msg("Invalid config-change cooldown - must be between 0 and 1 week")]
+    InvalidConfigChangeCooldown,
+    
+    #[msg("Invalid claim split - split_bps must be below 10000 and match a signed split destination other than the claimer's account")]
+    InvalidSplit,
+    
+    #[msg("Source and destination token accounts must be different")]
+    SelfTransfer,
+    
+    #[msg("Invalid post-claim hook program or missing hook accounts")]
+    InvalidPostClaimHook,
+    
+    #[msg("Lifetime claim quota exceeded for this user")]
+    ClaimQuotaExceeded,
+    
+    #[msg("Claim would push the frozen account balance above the configured cap")]
+    FrozenBalanceCapExceeded,
+    
+    #[msg("Treasury account must be owned by the token_state PDA with no delegate or close authority")]
+    InvalidTreasuryAuthority,
+    
+    #[msg("Sender is soft-locked and cannot transfer tokens")]
+    SoftLocked,
+    
+    #[msg("Invalid reward tiers - at most 8, strictly increasing min_claims, multipliers between 1 and 60000 bps")]
+    InvalidRewardTiers,
+    
+    #[msg("Mint or freeze authority is revoked or held by an account other than the admin or PDA")]
+    PdaAuthorityMismatch,
+    
+    #[msg("Claimer is not in the allowlist or the Merkle proof is invalid")]
+    NotAllowlisted,
+    
+    #[msg("Computed mint amount for a claim leg rounds to zero")]
+    ComputedAmountZero,
+    
+    #[msg("Claim would exceed the per-user daily amount cap")]
+    DailyAmountCapExceeded,
+    
+    #[msg("This instruction has been permanently disabled")]
+    InstructionDisabled,
+    
+    #[msg("Invalid instruction flag - must be a non-empty set of known instruction families")]
+    InvalidInstructionFlag,
+    
+    #[msg("Invalid clock skew tolerance - must be between 0 and 120 seconds")]
+    InvalidClockSkewTolerance,
+    
+    #[msg("Decimals mismatch - decimals cannot change once the token mint exists")]
+    DecimalsMismatch,
+    
+    #[msg("Range authorization exhausted - claim would exceed the signed total")]
+    RangeAuthorizationExhausted,
+    
+    #[msg("Inconsistent config - the combined settings are invalid")]
+    InconsistentConfig,
+    
+    #[msg("Signature claims are paused")]
+    SignatureClaimsPaused,
+    
+    #[msg("Insufficient claimer funds - wallet holds less SOL than the configured minimum")]
+    InsufficientClaimerFunds,
+    
+    #[msg("Self-mint forbidden - admin mints cannot target admin-owned accounts")]
+    SelfMintForbidden,
+    
+    #[msg("Freeze authority not PDA - the mint's freeze authority must be the token_state PDA")]
+    FreezeAuthorityNotPda,
+    
+    #[msg("Invalid schedule count - must be between 1 and 64")]
+    InvalidScheduleCount,
+    
+    #[msg("Claim schedule unavailable while the slot-based time-lock is active")]
+    ClaimScheduleUnavailable,
+    
+    #[msg("Destination denied - minting to this address is not allowed")]
+    DestinationDenied,
+    
+    #[msg("Mint denylist is full")]
+    MintDenylistFull,
+    
+    #[msg("Signed config has expired")]
+    SignedConfigExpired,
+    
+    #[msg("Claim expiry is too far in the future")]
+    ExpiryTooFar,
+    
+    #[msg("Invalid max future expiry - must not be negative")]
+    InvalidMaxFutureExpiry,
+    
+    #[msg("Transfer stats account required while transfer count tracking is enabled")]
+    InvalidTransferStats,
+    
+    #[msg("User has already claimed - single claim mode is enabled")]
+    AlreadyClaimed,
+    
+    #[msg("Claim reversal window has closed or reversals are disabled")]
+    ReversalWindowClosed,
+    
+    #[msg("Only the latest unreversed claim can be reversed, for at most its minted amount")]
+    InvalidReversal,
+    
+    #[msg("User token account has not delegated enough tokens to the program for the reversal")]
+    ReversalNotDelegated,
+    
+    #[msg("Reversal window must be between 0 and 86400 seconds")]
+    InvalidReversalWindow,
+    
+    #[msg("Signed message exceeds the maximum supported size")]
+    SignedMessageTooLarge,
+    
+    #[msg("Scheduled transfer enable time must be in the future, or 0 to cancel")]
+    InvalidTransferSchedule,
+    
+    #[msg("Source stats account missing or does not match the claim's source tag")]
+    InvalidSourceStats,
+    
+    #[msg("Treasury token account is not for the program's token mint")]
+    TreasuryMintMismatch,
+    
+    #[msg("Treasury transfer destination is the treasury itself")]
+    TreasuryTransferToSelf,
+    
+    #[msg("Claim window has not opened yet")]
+    ClaimWindowNotOpen,
+    
+    #[msg("Claim window has closed")]
+    ClaimWindowClosed,
+    
+    #[msg("Claim window open must be before close, with 0 meaning unbounded")]
+    InvalidClaimWindow,
+    
+    #[msg("Recovery signers must be distinct, at most 5, with a threshold between 1 and their count (or none with threshold 0)")]
+    InvalidRecoveryConfig,
+    
+    #[msg("Admin recovery is not configured")]
+    RecoveryNotConfigured,
+    
+    #[msg("An admin recovery is already pending")]
+    RecoveryAlreadyPending,
+    
+    #[msg("No admin recovery is pending")]
+    NoPendingRecovery,
+    
+    #[msg("Admin recovery timelock has not elapsed")]
+    RecoveryTimelockActive,
+    
+    #[msg("Not enough recovery signer signatures")]
+    InsufficientRecoverySignatures,
+    
+    #[msg("Recovered admin must be a new, non-default key")]
+    InvalidRecoveryAdmin,
+    
+    #[msg("Token name or symbol contains control or invisible characters, or leading/trailing whitespace")]
+    InvalidTokenName,
+    
+    #[msg("Admin history account is required once admin history is enabled")]
+    AdminHistoryRequired,
+    
+    #[msg("Transfer requires an admin approval signature, the instructions sysvar and the sender's transfer nonce")]
+    TransferApprovalRequired,
+    
+    #[msg("Lockup schedule needs 1 to MAX_TRANCHES tranches and a positive interval")]
+    InvalidLockupSchedule,
+    
+    #[msg("Mint lockup accounts are missing or do not match")]
+    InvalidMintLockup,
+    
+    #[msg("No vested tranche is available to release yet")]
+    NothingToRelease,
+    
+    #[msg("Claim amount is anomalously large compared to the recent average")]
+    AnomalousClaim,
+    
+    #[msg("Anomaly multiplier must be 0 (off) or at least 2")]
+    InvalidAnomalyMultiplier,
+    
+    #[msg("Mint lockup still holds unreleased tokens")]
+    LockupNotFullyReleased,
+    
+    #[msg("Claim burn must be below 10000 basis points")]
+    InvalidClaimBurnBps,
+    
+    #[msg("User data account is already at the current layout")]
+    UserDataAlreadyMigrated,
+    
+    #[msg("Mint index 0 is the primary mint - use mint_tokens or burn_tokens")]
+    PrimaryMintIndex,
+    
+    #[msg("Range authorization was superseded by a later range")]
+    RangeAuthorizationSuperseded,
+}
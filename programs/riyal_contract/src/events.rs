@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+/// Emitted whenever tokens move into or out of the contract treasury
+#[event]
+pub struct TreasuryEvent {
+    pub op: u8,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub balance_after: u64,
+    /// Mint decimals, so indexers can derive UI amounts without reading TokenState
+    pub decimals: u8,
+    pub timestamp: i64,
+}
+
+impl TreasuryEvent {
+    pub const OP_MINT: u8 = 0;
+    pub const OP_BURN: u8 = 1;
+    pub const OP_TRANSFER: u8 = 2;
+}
+
+/// Emitted for every successful claim
+#[event]
+pub struct ClaimEvent {
+    /// Deterministic id shared with the ClaimReceipt return data
+    pub claim_id: [u8; 32],
+    pub user: Pubkey,
+    pub amount: u64,
+    pub split_amount: u64,
+    pub nonce: u64,
+    /// Mint supply read after the claim's mint CPIs
+    pub supply_after: u64,
+    pub decimals: u8,
+    /// Key whose Ed25519 signature authorized the claim (claim_signer, or admin when unset)
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted for every program-mediated transfer, with balances read after the transfer CPI
+#[event]
+pub struct TransferEvent {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    /// Always 0 - the program charges no transfer fee
+    pub fee: u64,
+    pub from_balance_after: u64,
+    pub to_balance_after: u64,
+    /// Sender's TransferStats count after this transfer (0 when tracking is off)
+    pub transfer_count: u64,
+    pub decimals: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when reverse_claim burns back part or all of a user's most recent claim
+#[event]
+pub struct ClaimReversed {
+    pub user: Pubkey,
+    /// Nonce consumed by the reversed claim - it stays consumed
+    pub nonce: u64,
+    pub amount: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted at every step of an admin key recovery
+#[event]
+pub struct AdminRecoveryEvent {
+    pub op: u8,
+    /// Admin at the time of the step (the replaced admin on completion)
+    pub admin: Pubkey,
+    pub new_admin: Pubkey,
+    /// When the recovery may be finalized (0 once cancelled)
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+impl AdminRecoveryEvent {
+    pub const OP_INITIATED: u8 = 0;
+    pub const OP_CANCELLED: u8 = 1;
+    pub const OP_COMPLETED: u8 = 2;
+}
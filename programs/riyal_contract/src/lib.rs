@@ -1,15 +1,26 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, Mint, TokenAccount, freeze_account, thaw_account, FreezeAccount, ThawAccount, mint_to, burn, transfer, MintTo, Burn, Transfer};
+use anchor_spl::token::{Token, Mint, TokenAccount, freeze_account, thaw_account, FreezeAccount, ThawAccount, mint_to, burn, transfer, set_authority, close_account, MintTo, Burn, Transfer, SetAuthority, CloseAccount};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
 use anchor_lang::solana_program::program_option::COption;
 use anchor_lang::solana_program::{
     sysvar::instructions::{self},
     sysvar::clock::Clock,
     account_info::AccountInfo,
+    hash::{hash, hashv},
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    compute_units::sol_remaining_compute_units,
 };
 pub mod errors;
 use errors::*;
 pub mod signature;
-use signature::verify_admin_signature_only;
+use signature::{count_threshold_signers, verify_admin_signature_only};
+pub mod oracle;
+use oracle::{read_oracle_price, usd_cents_to_token_amount};
+pub mod events;
+use events::*;
+pub mod merkle;
+use merkle::{allowlist_leaf, verify_proof};
 
 declare_id!("DUALvp1DCViwVuWYPF66uPcdwiGXXLSW1pPXcAei3ihK");
 
@@ -20,13 +31,358 @@ pub struct ClaimPayload {
     pub claim_amount: u64,
     pub expiry_time: i64,
     pub nonce: u64,
+    /// Admin-authorized skip of the per-user cooldown for this claim only
+    pub bypass_time_lock: bool,
+    /// Share of the claim in basis points minted to `split_destination` (0 = no split, must be below 10000)
+    pub split_bps: u16,
+    /// Secondary (e.g. vesting) token account receiving the split share, never the claimer's own account
+    pub split_destination: Pubkey,
+    /// Campaign tag for attribution (0 = untagged), totals kept in the source's SourceStats PDA
+    pub source: u16,
+    /// Admin-authorized skip of the anomaly guard for a legitimately large claim
+    pub anomaly_override: bool,
+}
+
+/// Admin authorization for incremental claims against one signature
+/// Each claim consumes the user's current nonce, which must lie in `[nonce_start, nonce_end]`,
+/// and the summed claim amounts may not exceed `total_authorized`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RangeClaimPayload {
+    pub user_address: Pubkey,
+    pub nonce_start: u64,
+    pub nonce_end: u64,
+    pub total_authorized: u64,
+    pub expiry_time: i64,
+}
+
+/// Return data of every claim instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClaimReceipt {
+    /// Deterministic id, see `claim_id`
+    pub claim_id: [u8; 32],
+    pub user: Pubkey,
+    pub amount: u64,
+    pub split_amount: u64,
+    pub nonce: u64,
+    pub supply_after: u64,
+}
+
+/// Batched admin config change for update_config - `None` leaves a field unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ConfigUpdate {
+    pub claim_period_seconds: Option<i64>,
+    pub time_lock_enabled: Option<bool>,
+    pub claim_period_slots: Option<u64>,
+    pub use_slot_timelock: Option<bool>,
+    pub claim_period_growth_bps: Option<u16>,
+    pub clock_skew_tolerance: Option<i64>,
+    pub max_supply: Option<u64>,
+    pub reserved_for_treasury: Option<u64>,
+    pub max_claims_per_user: Option<u64>,
+    pub max_frozen_per_account: Option<u64>,
+    pub daily_amount_cap: Option<u64>,
+}
+
+/// Config change signed offline by the admin and submitted by any relayer via apply_signed_config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SignedConfig {
+    /// Must equal TokenState::config_nonce - each signed change applies at most once
+    pub nonce: u64,
+    pub expiry_time: i64,
+    pub config: ConfigUpdate,
+}
+
+/// Social-recovery set for the admin key, fixed at initialize
+/// `threshold` distinct `signers` can jointly start replacing a lost admin via recover_admin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct RecoveryConfig {
+    /// Empty disables recovery
+    pub signers: Vec<Pubkey>,
+    /// Must be 0 with no signers, otherwise between 1 and signers.len()
+    pub threshold: u8,
+}
+
+/// Optional tranche lockup for mint_tokens: the amount is held in a frozen escrow and
+/// release_tranche pays out `amount / tranche_count` every `tranche_interval_seconds`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LockupSchedule {
+    /// Between 1 and MintLockup::MAX_TRANCHES
+    pub tranche_count: u16,
+    /// Must be positive
+    pub tranche_interval_seconds: i64,
+}
+
+/// Per-user claim status returned by get_batch_claim_status
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UserClaimStatus {
+    pub nonce: u64,
+    pub total_claims: u64,
+    pub next_allowed_claim_time: i64,
+}
+
+/// Cooldown resolution returned by get_effective_cooldown
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EffectiveCooldown {
+    /// Rule that produced `cooldown`, one of cooldown_source::*
+    pub source: u8,
+    /// Cooldown the user's next claim will start - slots in slot mode, seconds otherwise
+    pub cooldown: i64,
+    /// Earliest time (seconds mode) or slot (slot mode) the next claim is accepted
+    pub next_allowed_claim_time: i64,
+    pub next_allowed_claim_slot: u64,
+}
+
+/// Sources reported in EffectiveCooldown::source
+pub mod cooldown_source {
+    /// Time-lock disabled - only the 1 second minimum gap applies
+    pub const MINIMUM_GAP: u8 = 0;
+    /// Flat claim_period_seconds
+    pub const GLOBAL: u8 = 1;
+    /// claim_period_seconds escalated by claim_period_growth_bps
+    pub const ESCALATED: u8 = 2;
+    /// claim_period_slots in slot time-lock mode
+    pub const SLOT: u8 = 3;
+}
+
+/// Deployment description returned by get_capabilities
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Capabilities {
+    /// TokenState::STATE_VERSION of the running program
+    pub state_version: u16,
+    /// capability::* bits active on this deployment
+    pub features: u64,
+    pub disabled_instructions: u64,
+    pub claim_period_seconds: i64,
+    pub claim_period_slots: u64,
+    pub max_supply: u64,
+    pub reserved_for_treasury: u64,
+    pub max_claims_per_user: u64,
+    pub daily_amount_cap: u64,
+    pub decimals: u8,
+}
+
+/// Mint binding diagnostic returned by verify_mint_binding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MintBindingReport {
+    /// Mint recorded in token_state
+    pub token_mint: Pubkey,
+    /// Mint account that was supplied
+    pub supplied_mint: Pubkey,
+    pub mint_matches: bool,
+    pub mint_authority_is_pda: bool,
+    pub freeze_authority_is_pda: bool,
+    pub decimals_match: bool,
+    pub supply: u64,
+}
+
+/// Feature bits reported in Capabilities::features
+pub mod capability {
+    pub const TIME_LOCK: u64 = 1 << 0;
+    pub const SLOT_TIME_LOCK: u64 = 1 << 1;
+    pub const ESCALATING_COOLDOWN: u64 = 1 << 2;
+    pub const ORACLE_PRICING: u64 = 1 << 3;
+    pub const ALLOWLIST: u64 = 1 << 4;
+    pub const SUPPLY_CAP: u64 = 1 << 5;
+    pub const CLAIM_QUOTA: u64 = 1 << 6;
+    pub const DAILY_AMOUNT_CAP: u64 = 1 << 7;
+    pub const REWARD_TIERS: u64 = 1 << 8;
+    pub const POST_CLAIM_HOOK: u64 = 1 << 9;
+    pub const SEPARATE_CLAIM_SIGNER: u64 = 1 << 10;
+    pub const TRANSFERS_ENABLED: u64 = 1 << 11;
+    pub const TRANSFERS_PERMANENT: u64 = 1 << 12;
+    pub const SIGNATURE_CLAIMS_PAUSED: u64 = 1 << 13;
+    pub const STRICT_SIG_POSITION: u64 = 1 << 14;
+    pub const SINGLE_CLAIM_MODE: u64 = 1 << 15;
+    pub const TRANSFER_APPROVAL: u64 = 1 << 16;
+    pub const ANOMALY_GUARD: u64 = 1 << 17;
+    pub const CLAIM_BURN: u64 = 1 << 18;
+}
+
+/// Supply figures returned by get_supply_stats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SupplyStats {
+    pub current_supply: u64,
+    pub max_supply: u64,
+    pub lifetime_minted: u64,
+    pub lifetime_burned: u64,
+    pub treasury_minted_total: u64,
+}
+
+/// Claim details passed to the optional post-claim hook program
+/// Instruction data is the Anchor discriminator of `post_claim` followed by this struct
+/// Forwarded accounts are never signers; on-chain user_data still holds the pre-claim values
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PostClaimHookData {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub split_amount: u64,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+/// Report bits returned by ensure_pda_authorities for each authority moved to the PDA
+pub const PDA_AUTHORITY_FIXED_MINT: u8 = 1;
+pub const PDA_AUTHORITY_FIXED_FREEZE: u8 = 2;
+
+/// Maximum user data accounts per get_batch_claim_status call (bounded by 1024-byte return data)
+pub const MAX_BATCH_STATUS_ACCOUNTS: usize = 40;
+
+/// Maximum user data accounts created per initialize_user_data_batch call (bounded by compute)
+pub const MAX_USER_DATA_BATCH: usize = 8;
+
+/// Maximum entries per get_claim_schedule call (bounded by 1024-byte return data)
+pub const MAX_CLAIM_SCHEDULE_ENTRIES: u8 = 64;
+
+/// Compute units kept in reserve per remaining batch item - below this the batch stops early
+pub const MIN_COMPUTE_PER_BATCH_ITEM: u64 = 5_000;
+
+/// Domain prefixes of the admin-signed messages, each followed by the program id and the payload
+pub const CLAIM_MESSAGE_PREFIX: &[u8] = b"RIYAL_CLAIM_V2";
+pub const CLAIM_RANGE_MESSAGE_PREFIX: &[u8] = b"RIYAL_CLAIM_RANGE_V1";
+pub const CONFIG_MESSAGE_PREFIX: &[u8] = b"RIYAL_CONFIG_V1";
+pub const CLAIM_TEXT_MESSAGE_PREFIX: &str = "RIYAL_CLAIM_TEXT_V1";
+pub const RECOVERY_MESSAGE_PREFIX: &[u8] = b"RIYAL_RECOVERY_V1";
+pub const TRANSFER_APPROVAL_MESSAGE_PREFIX: &[u8] = b"RIYAL_TRANSFER_V1";
+
+/// Version of the descriptor returned by get_message_schema - bump whenever a format changes
+pub const MESSAGE_SCHEMA_VERSION: u8 = 5;
+
+/// Signed message descriptor returned by get_message_schema
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MessageSchema {
+    pub version: u8,
+    pub formats: Vec<MessageFormat>,
+}
+
+/// One signed message format: `prefix | program_id | fields`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MessageFormat {
+    /// Name of the instruction(s) verifying this format
+    pub instruction: String,
+    pub prefix: String,
+    /// One of message_encoding::*
+    pub encoding: u8,
+    /// Fields in signed order
+    pub fields: Vec<MessageField>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MessageField {
+    pub name: String,
+    /// One of message_field_kind::*, or'd with OPTION for borsh Option fields
+    pub kind: u8,
+}
+
+/// Encodings reported in MessageFormat::encoding
+pub mod message_encoding {
+    /// prefix bytes | program id (32 bytes) | borsh(fields)
+    pub const BORSH: u8 = 0;
+    /// ASCII line built by claim_text_message
+    pub const TEXT: u8 = 1;
+}
+
+/// Field kinds reported in MessageField::kind
+pub mod message_field_kind {
+    pub const PUBKEY: u8 = 0;
+    pub const U64: u8 = 1;
+    pub const I64: u8 = 2;
+    pub const BOOL: u8 = 3;
+    pub const U16: u8 = 4;
+    /// Flag: borsh Option (1-byte tag, then the value when present)
+    pub const OPTION: u8 = 0x80;
+}
+
+/// Stable reason codes logged as `CLAIM_REJECT code=<n>` when claim_tokens rejects a claim
+/// Append-only - log scrapers aggregate on these values
+pub mod claim_reject {
+    pub const NOT_INITIALIZED: u16 = 1;
+    pub const MINT_NOT_CREATED: u16 = 2;
+    pub const INVALID_MINT: u16 = 3;
+    pub const INVALID_TOKEN_ACCOUNT: u16 = 4;
+    pub const UNAUTHORIZED_DESTINATION: u16 = 5;
+    pub const ZERO_AMOUNT: u16 = 6;
+    pub const INVALID_USER_DATA: u16 = 7;
+    pub const INVALID_NONCE: u16 = 8;
+    pub const REGION_BLOCKED: u16 = 9;
+    pub const TIME_LOCKED: u16 = 10;
+    pub const PERIOD_NOT_ELAPSED: u16 = 11;
+    pub const TOO_FREQUENT: u16 = 12;
+    pub const EXPIRED: u16 = 13;
+    pub const INVALID_SIGNATURE: u16 = 14;
+    pub const ORACLE_REJECTED: u16 = 15;
+    pub const RESERVE_VIOLATION: u16 = 16;
+    pub const INVALID_SPLIT: u16 = 17;
+    pub const QUOTA_EXCEEDED: u16 = 18;
+    pub const FROZEN_BALANCE_CAP: u16 = 19;
+    pub const NOT_ALLOWLISTED: u16 = 20;
+    pub const COMPUTED_AMOUNT_ZERO: u16 = 21;
+    pub const DAILY_AMOUNT_CAP: u16 = 22;
+    pub const RANGE_EXHAUSTED: u16 = 23;
+    pub const SIGNATURE_CLAIMS_PAUSED: u16 = 24;
+    pub const INSUFFICIENT_CLAIMER_FUNDS: u16 = 25;
+    pub const DESTINATION_DENIED: u16 = 26;
+    pub const EXPIRY_TOO_FAR: u16 = 27;
+    pub const ALREADY_CLAIMED: u16 = 28;
+    pub const INVALID_SOURCE_STATS: u16 = 29;
+    pub const WINDOW_NOT_OPEN: u16 = 30;
+    pub const WINDOW_CLOSED: u16 = 31;
+    pub const ANOMALOUS_CLAIM: u16 = 32;
+    pub const RANGE_SUPERSEDED: u16 = 33;
+}
+
+/// Instruction family bits for TokenState::disabled_instructions - disabling is permanent
+/// Instructions not listed under any family are never gated, notably release_tranche,
+/// unfreeze_account, unfreeze_token_account, unfreeze_managed_account, launch_transfers and
+/// enable_transfers - retiring a family does not stop those
+pub mod instruction_family {
+    /// claim_tokens, claim_tokens_text, claim_tokens_init, claim_tokens_range
+    pub const CLAIM: u64 = 1 << 0;
+    /// mint_tokens, mint_managed_tokens
+    pub const MINT: u64 = 1 << 1;
+    /// create_treasury, register_treasury, set_treasury, create_token_mint_with_treasury
+    pub const TREASURY_SETUP: u64 = 1 << 2;
+    /// mint_to_treasury
+    pub const TREASURY_MINT: u64 = 1 << 3;
+    /// burn_tokens, burn_from_treasury, burn_managed_tokens, reverse_claim
+    pub const BURN: u64 = 1 << 4;
+    /// freeze_token_account, freeze_managed_account
+    pub const FREEZE: u64 = 1 << 5;
+    /// add_managed_mint
+    pub const ADD_MINT: u64 = 1 << 6;
+    /// transfer_tokens, transfer_all (via transfer_tokens), unfreeze_and_transfer
+    pub const TRANSFER: u64 = 1 << 7;
+
+    pub const ALL: u64 = CLAIM | MINT | TREASURY_SETUP | TREASURY_MINT | BURN | FREEZE | ADD_MINT | TRANSFER;
+}
+
+/// Reject the instruction when its family has been permanently disabled
+macro_rules! require_enabled {
+    ($token_state:expr, $family:expr) => {
+        require!(
+            $token_state.disabled_instructions & $family == 0,
+            RiyalError::InstructionDisabled
+        )
+    };
+}
+
+/// `require!` for claim preconditions that logs a structured rejection line before failing
+/// The log survives in the failed transaction's metadata even though state is reverted
+macro_rules! require_claim {
+    ($cond:expr, $code:expr, $user:expr, $err:expr) => {
+        if !($cond) {
+            msg!("CLAIM_REJECT code={} user={}", $code, $user);
+            return Err($err.into());
+        }
+    };
 }
 
 #[program]
 pub mod riyal_contract {
     use super::*;
 
-    /// Initialize the contract with admin public key, time-lock settings, and upgrade authority
+    /// Initialize the contract with admin public key, time-lock settings, upgrade authority,
+    /// config-change cooldown and the optional admin recovery set
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         ctx: Context<Initialize>, 
         admin: Pubkey,
@@ -34,6 +390,8 @@ pub mod riyal_contract {
         claim_period_seconds: i64,
         time_lock_enabled: bool,
         upgradeable: bool,
+        config_change_cooldown: i64,
+        recovery: RecoveryConfig,
     ) -> Result<()> {
         let token_state = &mut ctx.accounts.token_state;
         
@@ -47,6 +405,18 @@ pub mod riyal_contract {
             claim_period_seconds <= 31536000, // Maximum 1 year
             RiyalError::InvalidClaimPeriod
         );
+
+        // Validate config-change cooldown (0 disables it)
+        require!(
+            (0..=604800).contains(&config_change_cooldown), // Maximum 1 week
+            RiyalError::InvalidConfigChangeCooldown
+        );
+
+        // Validate the optional social-recovery set (empty with threshold 0 disables recovery)
+        require!(
+            TokenState::is_valid_recovery_config(&recovery.signers, recovery.threshold),
+            RiyalError::InvalidRecoveryConfig
+        );
         
         token_state.admin = admin;
         token_state.upgrade_authority = upgrade_authority;
@@ -59,14 +429,66 @@ pub mod riyal_contract {
         token_state.claim_period_seconds = claim_period_seconds;
         token_state.time_lock_enabled = time_lock_enabled;
         token_state.upgradeable = upgradeable;
+        token_state.bump = ctx.bumps.token_state; // Cached for signer seeds and PDA validation
+        token_state.strict_sig_position = false; // Scan recent instructions by default
+        token_state.price_oracle = Pubkey::default(); // Will be set when oracle pricing is configured
+        token_state.use_oracle_pricing = false;
+        token_state.lifetime_minted = 0;
+        token_state.lifetime_burned = 0;
+        token_state.additional_mints = Vec::new();
+        token_state.blocked_regions = [0u8; 32]; // No regions blocked
+        token_state.max_supply = 0; // Uncapped until set by admin
+        token_state.reserved_for_treasury = 0;
+        token_state.metadata_locked = false;
+        token_state.use_slot_timelock = false; // Timestamp-based time-lock by default
+        token_state.claim_period_slots = 0;
+        token_state.config_change_cooldown = config_change_cooldown;
+        token_state.last_config_change = 0; // No config changes yet
+        token_state.post_claim_hook = Pubkey::default(); // Post-claim hook disabled by default
+        token_state.max_claims_per_user = 0; // No lifetime claim quota by default
+        token_state.max_frozen_per_account = 0; // No frozen balance cap by default
+        token_state.claim_period_growth_bps = 0; // Flat cooldown by default
+        token_state.quiet_logging = false; // Verbose logging by default
+        token_state.reward_tiers = Vec::new(); // No loyalty multipliers by default
+        token_state.claim_signer = Pubkey::default(); // Claims signed by the admin until a signer is set
+        token_state.treasury_minted_total = 0;
+        token_state.allowlist_root = [0u8; 32];
+        token_state.require_allowlist = false; // Open claiming until an allowlist is set
+        token_state.daily_amount_cap = 0; // No daily amount cap by default
+        token_state.disabled_instructions = 0; // Every instruction family enabled
+        token_state.clock_skew_tolerance = 0; // Exact expiry/time-lock comparisons by default
+        token_state.signature_claims_paused = false; // Signed claims live
+        token_state.min_claimer_sol = 0; // No claimer balance requirement
+        token_state.self_mint_forbidden = false; // Can be locked later via forbid_self_mint
+        token_state.mint_denylist = Vec::new(); // No denied mint destinations
+        token_state.config_nonce = 0; // First signed config uses nonce 0
+        token_state.max_future_expiry_seconds = 0; // Any future expiry accepted
+        token_state.track_transfer_counts = false; // Transfer analytics off by default
+        token_state.single_claim_mode = false; // Recurring claims allowed by default
+        token_state.refreeze_recipients = false; // Recipients keep received tokens thawed by default
+        token_state.reversal_window_seconds = 0; // Claim reversals disabled by default
+        token_state.transfers_enable_at = 0; // No scheduled transfer enable by default
+        token_state.claim_window_open = 0; // Claim window unbounded by default
+        token_state.claim_window_close = 0;
+        token_state.recovery_signers = recovery.signers;
+        token_state.recovery_threshold = recovery.threshold;
+        token_state.recovery_nonce = 0;
+        token_state.pending_recovery_admin = Pubkey::default(); // No recovery in progress
+        token_state.recovery_executable_at = 0;
+        token_state.admin_history_enabled = false; // No history PDA until initialize_admin_history
+        token_state.require_transfer_approval = false; // Transfers need no per-transfer approval by default
+        token_state.anomaly_multiplier = 0; // Anomaly guard off by default
+        token_state.claim_amount_ewma = 0;
+        token_state.claim_burn_bps = 0; // No burn-on-claim tax by default
         
         msg!(
-            "Contract initialized - Admin: {}, Upgrade Authority: {}, Claim Period: {}s, Time-lock: {}, Upgradeable: {}",
+            "Contract initialized - Admin: {}, Upgrade Authority: {}, Claim Period: {}s, Time-lock: {}, Upgradeable: {}, Config Cooldown: {}s",
             admin,
             upgrade_authority,
             claim_period_seconds,
             time_lock_enabled,
-            upgradeable
+            upgradeable,
+            config_change_cooldown
         );
         Ok(())
     }
@@ -98,6 +520,9 @@ pub mod riyal_contract {
             RiyalError::TokenMintAlreadyCreated
         );
 
+        // Reject oversized or UI-spoofing names and symbols
+        TokenState::validate_token_metadata(&name, &symbol)?;
+
         // Store token mint information
         token_state.token_mint = ctx.accounts.mint.key();
         token_state.token_name = name.clone();
@@ -118,7 +543,73 @@ pub mod riyal_contract {
         Ok(())
     }
 
+    /// Bootstrap: create the mint and its treasury ATA in one instruction (admin only)
+    /// The mint account is initialized before the treasury ATA, so the treasury never points at
+    /// the default pubkey and can never be created ahead of the mint
+    pub fn create_token_mint_with_treasury(
+        ctx: Context<CreateTokenMintWithTreasury>,
+        decimals: u8,
+        name: String,
+        symbol: String,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::TREASURY_SETUP);
+        
+        // Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL: Verify token mint hasn't been created already
+        require!(
+            token_state.token_mint == Pubkey::default(),
+            RiyalError::TokenMintAlreadyCreated
+        );
+
+        // Reject oversized or UI-spoofing names and symbols
+        TokenState::validate_token_metadata(&name, &symbol)?;
+
+        // CRITICAL: Verify treasury not already created
+        require!(
+            token_state.treasury_account == Pubkey::default(),
+            RiyalError::TreasuryAlreadyCreated
+        );
+
+        // Store token mint information
+        token_state.token_mint = ctx.accounts.mint.key();
+        token_state.token_name = name.clone();
+        token_state.token_symbol = symbol.clone();
+        token_state.decimals = decimals;
+        
+        // Start with transfers DISABLED (paused)
+        token_state.transfers_enabled = false;
+
+        // Store treasury account
+        token_state.treasury_account = ctx.accounts.treasury_account.key();
+
+        msg!(
+            "Token mint created: {} ({}) with {} decimals, mint authority: {}, treasury: {}, transfers: PAUSED",
+            name,
+            symbol,
+            decimals,
+            token_state.admin,
+            token_state.treasury_account
+        );
+
+        Ok(())
+    }
+
     /// Update token mint (admin only) - for migration purposes
+    /// Once a mint is recorded only name/symbol may change; mint and decimals are fixed
     pub fn update_token_mint(
         ctx: Context<UpdateTokenMint>,
         decimals: u8,
@@ -139,6 +630,51 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
+        // Reject oversized or UI-spoofing names and symbols
+        TokenState::validate_token_metadata(&name, &symbol)?;
+
+        // Verify name/symbol are not being changed once metadata is locked
+        require!(
+            !token_state.metadata_locked
+                || (name == token_state.token_name && symbol == token_state.token_symbol),
+            RiyalError::MetadataLocked
+        );
+
+        // Every freeze/thaw path needs the PDA as freeze authority - move it first (set_authority) for imported mints
+        require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
+        // Decimals must always agree with the mint account itself
+        require!(
+            ctx.accounts.mint.decimals == decimals,
+            RiyalError::DecimalsMismatch
+        );
+
+        if token_state.token_mint != Pubkey::default() {
+            // Changing decimals after issuance would reinterpret every balance
+            require!(
+                decimals == token_state.decimals,
+                RiyalError::DecimalsMismatch
+            );
+
+            // Post-creation updates are metadata-only
+            require!(
+                ctx.accounts.mint.key() == token_state.token_mint,
+                RiyalError::InvalidTokenMint
+            );
+
+            token_state.token_name = name.clone();
+            token_state.token_symbol = symbol.clone();
+
+            msg!(
+                "Token metadata UPDATED: {} ({}) for mint {}",
+                name,
+                symbol,
+                token_state.token_mint
+            );
+
+            return Ok(());
+        }
+
         // Store new token mint information
         token_state.token_mint = ctx.accounts.mint.key();
         token_state.token_name = name.clone();
@@ -149,7 +685,7 @@ pub mod riyal_contract {
         token_state.treasury_account = Pubkey::default();
 
         msg!(
-            "Token mint UPDATED: {} ({}) with {} decimals, mint authority: {}, OLD MINT REPLACED",
+            "Token mint UPDATED: {} ({}) with {} decimals, mint authority: {}",
             name,
             symbol,
             decimals,
@@ -159,26 +695,46 @@ pub mod riyal_contract {
         Ok(())
     }
 
-    /// Check if transfers are enabled (used by transfer functions)
-    pub fn check_transfers_enabled(ctx: Context<CheckTransfersEnabled>) -> Result<()> {
-        let token_state = &ctx.accounts.token_state;
+    /// Permanently disable one or more instruction families (admin only) - cannot be undone
+    /// See instruction_family for the instructions each bit covers and those no bit covers
+    pub fn disable_instruction(ctx: Context<DisableInstruction>, flag: u64) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
 
-        // Check if transfers are enabled
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
         require!(
-            token_state.transfers_enabled,
-            RiyalError::TransfersPaused
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Only known instruction_family bits may be set
+        require!(
+            flag != 0 && flag & !instruction_family::ALL == 0,
+            RiyalError::InvalidInstructionFlag
         );
 
+        // Bits are only ever added, never cleared
+        token_state.disabled_instructions |= flag;
+
         msg!(
-            "Transfers are enabled: {}",
-            token_state.transfers_enabled
+            "INSTRUCTIONS PERMANENTLY DISABLED: Admin: {}, Flag: {:#x}, Disabled set: {:#x}",
+            ctx.accounts.admin.key(),
+            flag,
+            token_state.disabled_instructions
         );
 
         Ok(())
     }
 
-    /// Pause token transfers (admin only)
-    pub fn pause_transfers(ctx: Context<PauseTransfers>) -> Result<()> {
+    /// Permanently lock token name and symbol (admin only) - cannot be undone
+    /// Locks the token_name/token_symbol stored in TokenState only; there is no Metaplex metadata
+    /// account, so no is_mutable update is made
+    pub fn lock_metadata(ctx: Context<LockMetadata>) -> Result<()> {
         let token_state = &mut ctx.accounts.token_state;
         
         // Verify admin is calling this function
@@ -193,24 +749,27 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
-        // Check if transfers are permanently enabled (cannot be paused)
+        // Verify metadata is not already locked
         require!(
-            !token_state.transfers_permanently_enabled,
-            RiyalError::TransfersPermanentlyEnabled
+            !token_state.metadata_locked,
+            RiyalError::MetadataLocked
         );
 
-        token_state.transfers_enabled = false;
+        token_state.metadata_locked = true;
 
         msg!(
-            "TRANSFERS PAUSED by admin: {}",
-            ctx.accounts.admin.key()
+            "METADATA PERMANENTLY LOCKED by admin: {} - Name: {}, Symbol: {} - CANNOT BE REVERSED",
+            ctx.accounts.admin.key(),
+            token_state.token_name,
+            token_state.token_symbol
         );
 
         Ok(())
     }
 
-    /// Resume token transfers (admin only)
-    pub fn resume_transfers(ctx: Context<ResumeTransfers>) -> Result<()> {
+    /// Permanently forbid admin mints to token accounts owned by the admin (admin only) - cannot be undone
+    /// Separation of duties: issuance must go to non-admin accounts or the treasury
+    pub fn forbid_self_mint(ctx: Context<ForbidSelfMint>) -> Result<()> {
         let token_state = &mut ctx.accounts.token_state;
         
         // Verify admin is calling this function
@@ -225,59 +784,107 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
-        token_state.transfers_enabled = true;
+        // Verify self-mint is not already forbidden
+        require!(
+            !token_state.self_mint_forbidden,
+            RiyalError::SelfMintForbidden
+        );
 
-        // Get current timestamp
-        let clock = Clock::get()?;
-        token_state.transfer_enable_timestamp = clock.unix_timestamp;
+        token_state.self_mint_forbidden = true;
 
         msg!(
-            "TRANSFERS RESUMED by admin: {} at timestamp: {}",
-            ctx.accounts.admin.key(),
-            clock.unix_timestamp
+            "SELF-MINT PERMANENTLY FORBIDDEN by admin: {} - CANNOT BE REVERSED",
+            ctx.accounts.admin.key()
         );
 
         Ok(())
     }
 
-    /// Permanently enable transfers (admin only) - cannot be undone
-    pub fn permanently_enable_transfers(ctx: Context<PermanentlyEnableTransfers>) -> Result<()> {
+    /// Check if transfers are enabled (used by transfer functions)
+    pub fn check_transfers_enabled(ctx: Context<CheckTransfersEnabled>) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Check if transfers are enabled, explicitly or by a passed schedule
+        let transfers_active = token_state.transfers_active(Clock::get()?.unix_timestamp);
+        require!(
+            transfers_active,
+            RiyalError::TransfersPaused
+        );
+
+        msg!(
+            "Transfers are enabled: {}",
+            transfers_active
+        );
+
+        Ok(())
+    }
+
+    /// Seconds since transfers were last enabled, or -1 if they never were
+    /// A schedule that has already passed counts as an enable at transfers_enable_at
+    pub fn seconds_since_transfers_enabled(ctx: Context<SecondsSinceTransfersEnabled>) -> Result<i64> {
+        let token_state = &ctx.accounts.token_state;
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let mut enabled_at = token_state.transfer_enable_timestamp;
+        if token_state.transfers_enable_at > 0 && current_timestamp >= token_state.transfers_enable_at {
+            enabled_at = enabled_at.max(token_state.transfers_enable_at);
+        }
+
+        if enabled_at == 0 {
+            return Ok(-1);
+        }
+
+        Ok(current_timestamp.saturating_sub(enabled_at))
+    }
+
+    /// Schedule transfer_tokens to open at `at` without an explicit enable call, 0 cancels (admin only)
+    /// The schedule behaves like resume_transfers, not enable_transfers: transfers are not made
+    /// permanent, so unfreezing still requires an explicit permanent enable
+    pub fn schedule_transfer_enable(ctx: Context<ScheduleTransferEnable>, at: i64) -> Result<()> {
         let token_state = &mut ctx.accounts.token_state;
         
-        // Verify admin is calling this function
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
             ctx.accounts.admin.key() == token_state.admin,
             RiyalError::UnauthorizedAdmin
         );
 
-        // Verify contract is initialized
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
         require!(
             token_state.is_initialized,
             RiyalError::ContractNotInitialized
         );
 
-        token_state.transfers_enabled = true;
-        token_state.transfers_permanently_enabled = true;
+        // CRITICAL SECURITY CHECK 3: Nothing to schedule once transfers are permanently enabled
+        require!(
+            !token_state.transfers_permanently_enabled,
+            RiyalError::TransfersAlreadyPermanentlyEnabled
+        );
 
-        // Get current timestamp
-        let clock = Clock::get()?;
-        token_state.transfer_enable_timestamp = clock.unix_timestamp;
+        // CRITICAL SECURITY CHECK 4: A schedule must lie in the future
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            at == 0 || at > current_timestamp,
+            RiyalError::InvalidTransferSchedule
+        );
+
+        let old_enable_at = token_state.transfers_enable_at;
+        token_state.transfers_enable_at = at;
 
         msg!(
-            "TRANSFERS PERMANENTLY ENABLED by admin: {} at timestamp: {} - CANNOT BE REVERSED",
+            "TRANSFER ENABLE SCHEDULED: Admin: {}, Enable at: {} → {}, Timestamp: {}",
             ctx.accounts.admin.key(),
-            clock.unix_timestamp
+            old_enable_at,
+            at,
+            current_timestamp
         );
 
         Ok(())
     }
 
-    /// Mint tokens to a user's token account (admin only)
-    pub fn mint_tokens(
-        ctx: Context<MintTokens>,
-        amount: u64,
-    ) -> Result<()> {
-        let token_state = &ctx.accounts.token_state;
+    /// Pause token transfers (admin only)
+    pub fn pause_transfers(ctx: Context<PauseTransfers>) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
         
         // Verify admin is calling this function
         require!(
@@ -291,18 +898,127 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
-        // Verify token mint has been created
+        // Check if transfers are permanently enabled (cannot be paused)
         require!(
-            token_state.token_mint != Pubkey::default(),
-            RiyalError::TokenMintNotCreated
+            !token_state.transfers_permanently_enabled,
+            RiyalError::TransfersPermanentlyEnabled
         );
 
-        // Verify the mint account matches the stored mint
-        require!(
-            ctx.accounts.mint.key() == token_state.token_mint,
+        token_state.transfers_enabled = false;
+        // A pending schedule would silently re-enable transfers - pausing cancels it
+        token_state.transfers_enable_at = 0;
+
+        msg!(
+            "TRANSFERS PAUSED by admin: {}",
+            ctx.accounts.admin.key()
+        );
+
+        Ok(())
+    }
+
+    /// Resume token transfers (admin only)
+    pub fn resume_transfers(ctx: Context<ResumeTransfers>) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        token_state.transfers_enabled = true;
+
+        // Get current timestamp
+        let clock = Clock::get()?;
+        token_state.transfer_enable_timestamp = clock.unix_timestamp;
+
+        msg!(
+            "TRANSFERS RESUMED by admin: {} at timestamp: {}",
+            ctx.accounts.admin.key(),
+            clock.unix_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Permanently enable transfers (admin only) - cannot be undone
+    pub fn permanently_enable_transfers(ctx: Context<PermanentlyEnableTransfers>) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        token_state.transfers_enabled = true;
+        token_state.transfers_permanently_enabled = true;
+
+        // Get current timestamp
+        let clock = Clock::get()?;
+        token_state.transfer_enable_timestamp = clock.unix_timestamp;
+
+        msg!(
+            "TRANSFERS PERMANENTLY ENABLED by admin: {} at timestamp: {} - CANNOT BE REVERSED",
+            ctx.accounts.admin.key(),
+            clock.unix_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Mint tokens to a user's token account (admin only)
+    /// With `lockup`, the amount is minted into a frozen escrow instead and vests in tranches,
+    /// paid out to the user's account by release_tranche
+    pub fn mint_tokens(
+        ctx: Context<MintTokens>,
+        amount: u64,
+        lockup: Option<LockupSchedule>,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::MINT);
+        
+        // Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // Verify the mint account matches the stored mint
+        require!(
+            ctx.accounts.mint.key() == token_state.token_mint,
             RiyalError::InvalidTokenMint
         );
 
+        // Freeze/thaw CPIs below need the PDA as freeze authority (imported mints may lack it)
+        require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
         // Verify the token account is for the correct mint
         require!(
             ctx.accounts.user_token_account.mint == token_state.token_mint,
@@ -315,17 +1031,70 @@ pub mod riyal_contract {
             RiyalError::InvalidMintAmount
         );
 
+        // Separation of duties: the admin may not mint to its own accounts once forbidden
+        require!(
+            !token_state.self_mint_forbidden || ctx.accounts.user_token_account.owner != ctx.accounts.admin.key(),
+            RiyalError::SelfMintForbidden
+        );
+
+        // Verify the destination is not denylisted
+        require!(
+            !token_state.is_mint_denied(&ctx.accounts.user_token_account.owner)
+                && !token_state.is_mint_denied(&ctx.accounts.user_token_account.key()),
+            RiyalError::DestinationDenied
+        );
+
+        // Verify the mint stays within the supply cap minus the treasury reserve
+        if let Some(headroom) = token_state.user_mint_headroom(ctx.accounts.mint.supply) {
+            require!(
+                amount <= headroom,
+                RiyalError::ReserveViolation
+            );
+        }
+
+        // Locked mints go to the freshly created escrow; lockup accounts are passed exactly when locking
+        if let Some(schedule) = lockup {
+            require!(
+                schedule.tranche_count > 0
+                    && schedule.tranche_count <= MintLockup::MAX_TRANCHES
+                    && schedule.tranche_interval_seconds > 0,
+                RiyalError::InvalidLockupSchedule
+            );
+        }
+        require!(
+            ctx.accounts.mint_lockup.is_some() == lockup.is_some()
+                && ctx.accounts.lockup_escrow.is_some() == lockup.is_some(),
+            RiyalError::InvalidMintLockup
+        );
+        let destination = match (&lockup, &ctx.accounts.lockup_escrow) {
+            (Some(_), Some(lockup_escrow)) => lockup_escrow.to_account_info(),
+            _ => ctx.accounts.user_token_account.to_account_info(),
+        };
+
         // Create PDA signer for minting
         let seeds = &[
             b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
+            &[ctx.accounts.token_state.bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
+        // Accounts left frozen by an earlier mint must be thawed first - they are re-frozen below
+        if lockup.is_none() && ctx.accounts.user_token_account.is_frozen() {
+            let thaw_cpi_accounts = ThawAccount {
+                account: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+            let thaw_cpi_ctx = CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds);
+
+            thaw_account(thaw_cpi_ctx)?;
+        }
+
         // Create CPI context for minting with PDA as authority
         let cpi_accounts = MintTo {
             mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
+            to: destination.clone(),
             authority: ctx.accounts.token_state.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -337,12 +1106,12 @@ pub mod riyal_contract {
         // AUTO-FREEZE: Immediately freeze the token account after minting
         let freeze_seeds = &[
             b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
+            &[ctx.accounts.token_state.bump],
         ];
         let freeze_signer_seeds = &[&freeze_seeds[..]];
 
         let freeze_cpi_accounts = FreezeAccount {
-            account: ctx.accounts.user_token_account.to_account_info(),
+            account: destination,
             mint: ctx.accounts.mint.to_account_info(),
             authority: ctx.accounts.token_state.to_account_info(),
         };
@@ -352,11 +1121,183 @@ pub mod riyal_contract {
         // Freeze the account immediately after minting
         freeze_account(freeze_cpi_ctx)?;
 
+        // Track lifetime issuance in the same instruction as the mint
+        let token_state = &mut ctx.accounts.token_state;
+        token_state.lifetime_minted = token_state.lifetime_minted
+            .checked_add(amount)
+            .ok_or(RiyalError::SupplyCounterOverflow)?;
+
+        if let (Some(schedule), Some(mint_lockup), Some(lockup_escrow)) =
+            (lockup, ctx.accounts.mint_lockup.as_mut(), ctx.accounts.lockup_escrow.as_ref())
+        {
+            mint_lockup.beneficiary_token_account = ctx.accounts.user_token_account.key();
+            mint_lockup.escrow = lockup_escrow.key();
+            mint_lockup.total_amount = amount;
+            mint_lockup.released_amount = 0;
+            mint_lockup.start_time = Clock::get()?.unix_timestamp;
+            mint_lockup.tranche_count = schedule.tranche_count;
+            mint_lockup.tranche_interval_seconds = schedule.tranche_interval_seconds;
+            mint_lockup.bump = ctx.bumps.mint_lockup.ok_or(RiyalError::InvalidMintLockup)?;
+
+            msg!(
+                "Minted {} tokens into lockup escrow: {} for user account: {} by admin: {} - {} TRANCHES EVERY {}s",
+                amount,
+                lockup_escrow.key(),
+                ctx.accounts.user_token_account.key(),
+                ctx.accounts.admin.key(),
+                schedule.tranche_count,
+                schedule.tranche_interval_seconds
+            );
+        } else {
+            msg!(
+                "Minted {} tokens to user account: {} by admin: {} - ACCOUNT IMMEDIATELY FROZEN",
+                amount,
+                ctx.accounts.user_token_account.key(),
+                ctx.accounts.admin.key()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Release every vested tranche of a mint lockup to its beneficiary (permissionless)
+    /// Tokens always go to the recorded beneficiary; a frozen beneficiary account is re-frozen after
+    pub fn release_tranche(ctx: Context<ReleaseTranche>) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // CRITICAL SECURITY CHECK 1: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Freeze/thaw CPIs below need the PDA as freeze authority (imported mints may lack it)
+        require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
+        // CRITICAL SECURITY CHECK 2: Something must have vested since the last release
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let releasable = ctx.accounts.mint_lockup
+            .vested_amount(current_timestamp)
+            .saturating_sub(ctx.accounts.mint_lockup.released_amount);
+        require!(
+            releasable > 0,
+            RiyalError::NothingToRelease
+        );
+
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let beneficiary_was_frozen = ctx.accounts.beneficiary_token_account.is_frozen();
+
+        // Thaw the escrow (frozen since the mint) and, if needed, the beneficiary for the transfer
+        let mut thawed = vec![ctx.accounts.lockup_escrow.to_account_info()];
+        if beneficiary_was_frozen {
+            thawed.push(ctx.accounts.beneficiary_token_account.to_account_info());
+        }
+        for account in thawed.iter() {
+            let thaw_cpi_accounts = ThawAccount {
+                account: account.clone(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+            thaw_account(CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds))?;
+        }
+
+        let transfer_cpi_accounts = Transfer {
+            from: ctx.accounts.lockup_escrow.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let transfer_cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new_with_signer(transfer_cpi_program, transfer_cpi_accounts, signer_seeds), releasable)?;
+
+        // The escrow stays frozen between releases; the beneficiary keeps its prior freeze state
+        for account in thawed {
+            let freeze_cpi_accounts = FreezeAccount {
+                account,
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let freeze_cpi_program = ctx.accounts.token_program.to_account_info();
+            freeze_account(CpiContext::new_with_signer(freeze_cpi_program, freeze_cpi_accounts, signer_seeds))?;
+        }
+
+        let mint_lockup = &mut ctx.accounts.mint_lockup;
+        mint_lockup.released_amount = mint_lockup.released_amount
+            .checked_add(releasable)
+            .ok_or(RiyalError::InvalidMintLockup)?;
+
         msg!(
-            "Minted {} tokens to user account: {} by admin: {} - ACCOUNT IMMEDIATELY FROZEN",
-            amount,
-            ctx.accounts.user_token_account.key(),
-            ctx.accounts.admin.key()
+            "TRANCHE RELEASED: Beneficiary: {}, Amount: {}, Released: {}/{}, Timestamp: {}",
+            mint_lockup.beneficiary_token_account,
+            releasable,
+            mint_lockup.released_amount,
+            mint_lockup.total_amount,
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Close a fully released mint lockup and its empty escrow, refunding the rent to the admin (admin only)
+    pub fn close_mint_lockup(ctx: Context<CloseMintLockup>) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Every tranche must have been paid out
+        require!(
+            ctx.accounts.mint_lockup.released_amount == ctx.accounts.mint_lockup.total_amount
+                && ctx.accounts.lockup_escrow.amount == 0,
+            RiyalError::LockupNotFullyReleased
+        );
+
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Frozen accounts cannot be closed
+        if ctx.accounts.lockup_escrow.is_frozen() {
+            require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
+            let thaw_cpi_accounts = ThawAccount {
+                account: ctx.accounts.lockup_escrow.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+            thaw_account(CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds))?;
+        }
+
+        let close_cpi_accounts = CloseAccount {
+            account: ctx.accounts.lockup_escrow.to_account_info(),
+            destination: ctx.accounts.admin.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let close_cpi_program = ctx.accounts.token_program.to_account_info();
+        close_account(CpiContext::new_with_signer(close_cpi_program, close_cpi_accounts, signer_seeds))?;
+
+        msg!(
+            "MINT LOCKUP CLOSED: Admin: {}, Beneficiary: {}, Total released: {}",
+            ctx.accounts.admin.key(),
+            ctx.accounts.mint_lockup.beneficiary_token_account,
+            ctx.accounts.mint_lockup.total_amount
         );
 
         Ok(())
@@ -365,6 +1306,9 @@ pub mod riyal_contract {
     /// Freeze a user's token account (admin only) - prevents all transfers
     pub fn freeze_token_account(ctx: Context<FreezeTokenAccount>) -> Result<()> {
         let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::FREEZE);
         
         // Verify admin is calling this function
         require!(
@@ -384,6 +1328,9 @@ pub mod riyal_contract {
             RiyalError::InvalidTokenMint
         );
 
+        // Freeze/thaw CPIs below need the PDA as freeze authority (imported mints may lack it)
+        require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
         // Verify the token account belongs to this mint
         require!(
             ctx.accounts.token_account.mint == token_state.token_mint,
@@ -393,7 +1340,7 @@ pub mod riyal_contract {
         // Create signer seeds for PDA authority
         let seeds = &[
             b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
+            &[ctx.accounts.token_state.bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
@@ -419,6 +1366,8 @@ pub mod riyal_contract {
     }
 
     /// Unfreeze a user's token account (admin only) - allows transfers again
+    /// Targeted admin thaw: not gated on transfers_permanently_enabled, unlike the
+    /// user self-service unfreeze_account
     pub fn unfreeze_token_account(ctx: Context<UnfreezeTokenAccount>) -> Result<()> {
         let token_state = &ctx.accounts.token_state;
         
@@ -440,6 +1389,9 @@ pub mod riyal_contract {
             RiyalError::InvalidTokenMint
         );
 
+        // Freeze/thaw CPIs below need the PDA as freeze authority (imported mints may lack it)
+        require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
         // Verify the token account belongs to this mint
         require!(
             ctx.accounts.token_account.mint == token_state.token_mint,
@@ -449,7 +1401,7 @@ pub mod riyal_contract {
         // Create signer seeds for PDA authority
         let seeds = &[
             b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
+            &[ctx.accounts.token_state.bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
@@ -485,6 +1437,15 @@ pub mod riyal_contract {
         user_data.next_allowed_claim_time = 0; // Can claim immediately on first attempt
         user_data.total_claims = 0;
         user_data.bump = ctx.bumps.user_data;
+        user_data.region = 0; // Untagged until set by admin
+        user_data.last_claim_slot = 0;
+        user_data.next_allowed_claim_slot = 0;
+        user_data.amount_today = 0;
+        user_data.day_index = 0;
+        user_data.range_nonce_start = 0;
+        user_data.range_consumed = 0;
+        user_data.last_claim_amount = 0;
+        user_data.last_claim_daily_amount = 0;
 
         msg!(
             "User data initialized for user: {} with nonce: {} at timestamp: {}, next claim allowed immediately",
@@ -496,216 +1457,245 @@ pub mod riyal_contract {
         Ok(())
     }
 
-    /// Claim tokens using admin-signed payload with user verification
-    pub fn claim_tokens(
-        ctx: Context<ClaimTokens>,
-        payload: ClaimPayload,
-        admin_signature: [u8; 64],
+    /// Pre-create user data PDAs for a known recipient list (admin pays rent)
+    /// remaining_accounts are (owner, user_data PDA) pairs; already initialized PDAs are skipped
+    pub fn initialize_user_data_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeUserDataBatch<'info>>,
     ) -> Result<()> {
         let token_state = &ctx.accounts.token_state;
-        let user_data = &mut ctx.accounts.user_data;
-        
-        // Verify contract is initialized
-        require!(
-            token_state.is_initialized,
-            RiyalError::ContractNotInitialized
-        );
-
-        // Verify token mint has been created
-        require!(
-            token_state.token_mint != Pubkey::default(),
-            RiyalError::TokenMintNotCreated
-        );
 
-        // Verify the mint account matches the stored mint
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
-            ctx.accounts.mint.key() == token_state.token_mint,
-            RiyalError::InvalidTokenMint
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
         );
 
-        // Verify the token account is for the correct mint
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
         require!(
-            ctx.accounts.user_token_account.mint == token_state.token_mint,
-            RiyalError::InvalidTokenAccount
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
         );
 
-        // CRITICAL: This check should come FIRST
+        // CRITICAL SECURITY CHECK 3: Accounts come in (owner, user_data) pairs within the compute cap
         require!(
-        payload.user_address == ctx.accounts.user.key(),
-        RiyalError::UnauthorizedDestination
+            ctx.remaining_accounts.len().is_multiple_of(2),
+            RiyalError::InvalidUserData
         );
-        // CRITICAL SECURITY: Verify destination binding - user can only claim to their own token account
         require!(
-            ctx.accounts.user_token_account.owner == ctx.accounts.user.key(),
-            RiyalError::UnauthorizedDestination
+            ctx.remaining_accounts.len() <= MAX_USER_DATA_BATCH * 2,
+            RiyalError::TooManyAccounts
         );
 
-        // Verify amount is not zero
-        require!(
-            payload.claim_amount > 0,
-            RiyalError::InvalidMintAmount
-        );
+        let rent_lamports = Rent::get()?.minimum_balance(UserData::SIZE);
+        let mut created = 0usize;
+        for pair in ctx.remaining_accounts.chunks_exact(2) {
+            let (owner, user_data_info) = (&pair[0], &pair[1]);
 
-        // Get current timestamp for validation
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
+            // Each PDA must derive from its owner
+            let (expected_pda, bump) = Pubkey::find_program_address(
+                &[b"user_data", owner.key.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                user_data_info.key() == expected_pda,
+                RiyalError::InvalidUserData
+            );
 
-        // CRITICAL SECURITY CHECK 1: Verify user data belongs to the user
-        require!(
-            user_data.user == ctx.accounts.user.key(),
-            RiyalError::InvalidUserData
-        );
+            // init_if_needed: existing accounts keep their nonce and history
+            if user_data_info.owner == ctx.program_id {
+                continue;
+            }
 
-        // CRITICAL SECURITY CHECK 2: Verify nonce matches user's current nonce (prevent replay attacks)
-        require!(
-            payload.nonce == user_data.nonce,
-            RiyalError::InvalidNonce
+            let bump_seed = [bump];
+            let signer_seeds: &[&[&[u8]]] = &[&[b"user_data", owner.key.as_ref(), &bump_seed]];
+            let system_program = ctx.accounts.system_program.to_account_info();
+            if user_data_info.lamports() == 0 {
+                let cpi_accounts = anchor_lang::system_program::CreateAccount {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: user_data_info.clone(),
+                };
+                anchor_lang::system_program::create_account(
+                    CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds),
+                    rent_lamports,
+                    UserData::SIZE as u64,
+                    ctx.program_id,
+                )?;
+            } else {
+                // Pre-funded address: top up rent, then allocate and assign it to this program
+                let lamports_needed = rent_lamports.saturating_sub(user_data_info.lamports());
+                if lamports_needed > 0 {
+                    let cpi_accounts = anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: user_data_info.clone(),
+                    };
+                    anchor_lang::system_program::transfer(
+                        CpiContext::new(system_program.clone(), cpi_accounts),
+                        lamports_needed,
+                    )?;
+                }
+                let allocate_accounts = anchor_lang::system_program::Allocate {
+                    account_to_allocate: user_data_info.clone(),
+                };
+                anchor_lang::system_program::allocate(
+                    CpiContext::new_with_signer(system_program.clone(), allocate_accounts, signer_seeds),
+                    UserData::SIZE as u64,
+                )?;
+                let assign_accounts = anchor_lang::system_program::Assign {
+                    account_to_assign: user_data_info.clone(),
+                };
+                anchor_lang::system_program::assign(
+                    CpiContext::new_with_signer(system_program, assign_accounts, signer_seeds),
+                    ctx.program_id,
+                )?;
+            }
+
+            let user_data = UserData {
+                user: owner.key(),
+                nonce: 0,
+                last_claim_timestamp: 0, // No claims yet
+                next_allowed_claim_time: 0, // Can claim immediately on first attempt
+                total_claims: 0,
+                bump,
+                region: 0, // Untagged until set by admin
+                last_claim_slot: 0,
+                next_allowed_claim_slot: 0,
+                amount_today: 0,
+                day_index: 0,
+                range_nonce_start: 0,
+                range_consumed: 0,
+                last_claim_amount: 0,
+                last_claim_daily_amount: 0,
+            };
+            let mut data = user_data_info.try_borrow_mut_data()?;
+            user_data.try_serialize(&mut &mut data[..])?;
+            created += 1;
+        }
+
+        msg!(
+            "USER DATA BATCH INITIALIZED: Admin: {}, Created: {}, Already existing: {}",
+            ctx.accounts.admin.key(),
+            created,
+            ctx.remaining_accounts.len() / 2 - created
         );
 
-        // CRITICAL SECURITY CHECK 5: TIME-LOCK VALIDATION - enforce claim periods
-        if token_state.time_lock_enabled {
-            // Check if enough time has passed since last claim
-            require!(
-                current_timestamp >= user_data.next_allowed_claim_time,
-                RiyalError::ClaimTimeLocked
-            );
-            
-            // For first-time claims, allow immediately
-            if user_data.total_claims > 0 {
-                require!(
-                    current_timestamp >= user_data.last_claim_timestamp.saturating_add(token_state.claim_period_seconds),
-                    RiyalError::ClaimPeriodNotElapsed
-                );
-            }
-        } else {
-            // If time-lock disabled, still enforce minimum 1 second gap
-            if user_data.last_claim_timestamp > 0 {
-                require!(
-                    current_timestamp > user_data.last_claim_timestamp,
-                    RiyalError::ClaimTooSoon
-                );
-                
-                require!(
-                    current_timestamp >= user_data.last_claim_timestamp.saturating_add(1),
-                    RiyalError::ClaimTooFrequent
-                );
-            }
-        }
+        Ok(())
+    }
 
-        // CRITICAL SECURITY CHECK 6: Validate nonce progression
-        if user_data.total_claims > 0 {
-            require!(
-                payload.nonce == user_data.nonce,
-                RiyalError::InvalidNonceSequence
-            );
-        }
+    /// Claim tokens using admin-signed payload with user verification
+    pub fn claim_tokens(
+        ctx: Context<ClaimTokens>,
+        payload: ClaimPayload,
+        admin_signature: [u8; 64],
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<ClaimReceipt> {
+        process_claim(ctx, payload, admin_signature, allowlist_proof, ClaimMessage::Binary)
+    }
 
-        // CRITICAL SECURITY: Validate expiry timestamp
-        require!(
-            current_timestamp <= payload.expiry_time,
-            RiyalError::ClaimExpired
-        );
-        
-        // Serialize the payload to create the message that was signed by admin
-        let payload_bytes = payload.try_to_vec().map_err(|_| RiyalError::InvalidClaimPayload)?;
-        
-        // Create DOMAIN-SEPARATED MESSAGE with the payload
-        // Format: "RIYAL_CLAIM_V2" | program_id | payload_bytes
-        let mut message_bytes = Vec::new();
-        message_bytes.extend_from_slice(b"RIYAL_CLAIM_V2");
-        message_bytes.extend_from_slice(&crate::ID.to_bytes());
-        message_bytes.extend_from_slice(&payload_bytes);
+    /// Claim tokens using an admin signature over the human-readable claim text
+    /// For hardware wallets that can only display and sign ASCII messages - the signed
+    /// message is exactly `claim_text_message(program_id, payload)`, see its docs for the format
+    pub fn claim_tokens_text(
+        ctx: Context<ClaimTokens>,
+        payload: ClaimPayload,
+        admin_signature: [u8; 64],
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<ClaimReceipt> {
+        process_claim(ctx, payload, admin_signature, allowlist_proof, ClaimMessage::Text)
+    }
 
-        // CRITICAL SECURITY: Verify admin signature format
-        require!(
-            admin_signature.len() == 64,
-            RiyalError::InvalidAdminSignature
-        );
+    /// Claim part of a nonce-range authorization - one admin signature covers every nonce in
+    /// `[nonce_start, nonce_end]` until the range or `total_authorized` is used up
+    /// The signed message is "RIYAL_CLAIM_RANGE_V1" | program_id | borsh(range_payload)
+    pub fn claim_tokens_range(
+        ctx: Context<ClaimTokens>,
+        range_payload: RangeClaimPayload,
+        claim_amount: u64,
+        admin_signature: [u8; 64],
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<ClaimReceipt> {
+        // Each call consumes the current nonce, no bypass or split under a range authorization
+        let payload = ClaimPayload {
+            user_address: range_payload.user_address,
+            claim_amount,
+            expiry_time: range_payload.expiry_time,
+            nonce: ctx.accounts.user_data.nonce,
+            bypass_time_lock: false,
+            split_bps: 0,
+            split_destination: Pubkey::default(),
+            source: 0,
+            anomaly_override: false,
+        };
 
-        // Verify signature is not empty
-        let admin_sig_sum: u64 = admin_signature.iter().map(|&x| x as u64).sum();
-        require!(
-            admin_sig_sum > 0,
-            RiyalError::InvalidAdminSignature
-        );
+        process_claim(ctx, payload, admin_signature, allowlist_proof, ClaimMessage::Range(range_payload))
+    }
 
-        // ENHANCED SECURITY: Verify only admin signature using Ed25519 program
-        // This requires an Ed25519 verify instruction to be included in the transaction
-        verify_admin_signature_only(
-            &ctx.accounts.instructions,
-            &message_bytes,
-            &admin_signature,
-            &token_state.admin,
-        )?;
+    /// Claim tokens, creating the user's data account first if it does not exist yet (user pays rent)
+    /// An existing user_data account is left untouched, so this path can never reset a nonce
+    pub fn claim_tokens_init<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimTokensInit<'info>>,
+        payload: ClaimPayload,
+        admin_signature: [u8; 64],
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<ClaimReceipt> {
+        let user_data = &mut ctx.accounts.user_data;
 
-        // Create PDA signer for minting (using token_state as authority)
-        let seeds = &[
-            b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        // Only a freshly created account still has the default user key
+        if user_data.user == Pubkey::default() {
+            user_data.user = ctx.accounts.user.key();
+            user_data.nonce = 0;
+            user_data.last_claim_timestamp = 0; // No claims yet
+            user_data.next_allowed_claim_time = 0; // Can claim immediately on first attempt
+            user_data.total_claims = 0;
+            user_data.bump = ctx.bumps.user_data;
+            user_data.region = 0; // Untagged until set by admin
+            user_data.last_claim_slot = 0;
+            user_data.next_allowed_claim_slot = 0;
+            user_data.amount_today = 0;
+            user_data.day_index = 0;
+            user_data.range_nonce_start = 0;
+            user_data.range_consumed = 0;
+            user_data.last_claim_amount = 0;
+            user_data.last_claim_daily_amount = 0;
+
+            msg!(
+                "User data initialized for user: {} during first claim",
+                user_data.user
+            );
+        }
 
-        // Create CPI context for minting with PDA as authority
-        let cpi_accounts = MintTo {
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.token_state.to_account_info(),
+        // Run the regular claim flow against the same accounts
+        let mut claim_accounts = ClaimTokens {
+            token_state: ctx.accounts.token_state.clone(),
+            user_data: ctx.accounts.user_data.clone(),
+            mint: ctx.accounts.mint.clone(),
+            user_token_account: ctx.accounts.user_token_account.clone(),
+            user: ctx.accounts.user.clone(),
+            instructions: ctx.accounts.instructions.clone(),
+            price_oracle: ctx.accounts.price_oracle.clone(),
+            split_token_account: ctx.accounts.split_token_account.clone(),
+            token_program: ctx.accounts.token_program.clone(),
+            source_stats: ctx.accounts.source_stats.clone(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-
-        // Mint tokens first
-        mint_to(cpi_ctx, payload.claim_amount)?;
-
-        // CRITICAL SECURITY: Immediately freeze the account after minting to prevent transfers
-        let freeze_seeds = &[
-            b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
-        ];
-        let freeze_signer_seeds = &[&freeze_seeds[..]];
-
-        let freeze_cpi_accounts = FreezeAccount {
-            account: ctx.accounts.user_token_account.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            authority: ctx.accounts.token_state.to_account_info(),
+        let claim_bumps = ClaimTokensBumps {
+            user_data: ctx.bumps.user_data,
         };
-        let freeze_cpi_program = ctx.accounts.token_program.to_account_info();
-        let freeze_cpi_ctx = CpiContext::new_with_signer(freeze_cpi_program, freeze_cpi_accounts, freeze_signer_seeds);
-
-        // Freeze the account immediately after claiming
-        freeze_account(freeze_cpi_ctx)?;
+        let receipt = claim_tokens(
+            Context::new(ctx.program_id, &mut claim_accounts, ctx.remaining_accounts, claim_bumps),
+            payload,
+            admin_signature,
+            allowlist_proof,
+        )?;
 
-        // CRITICAL SECURITY UPDATE: Increment nonce and update security tracking
-        let old_nonce = user_data.nonce;
-        user_data.nonce = user_data.nonce.checked_add(1)
-            .ok_or(RiyalError::NonceOverflow)?;
-        
-        // Update timestamp and claim count for additional security tracking
-        user_data.last_claim_timestamp = current_timestamp;
-        user_data.total_claims = user_data.total_claims.checked_add(1)
-            .ok_or(RiyalError::ClaimCountOverflow)?;
-        
-        // CRITICAL TIME-LOCK UPDATE: Set next allowed claim time
-        if token_state.time_lock_enabled {
-            user_data.next_allowed_claim_time = current_timestamp
-                .checked_add(token_state.claim_period_seconds)
-                .ok_or(RiyalError::TimestampOverflow)?;
-        } else {
-            // If time-lock disabled, allow next claim after 1 second
-            user_data.next_allowed_claim_time = current_timestamp.saturating_add(1);
+        // Carry the claim's state updates back so they are persisted on exit
+        ctx.accounts.token_state.set_inner(claim_accounts.token_state.into_inner());
+        ctx.accounts.user_data.set_inner(claim_accounts.user_data.into_inner());
+        if let (Some(source_stats), Some(claimed_source_stats)) =
+            (ctx.accounts.source_stats.as_mut(), claim_accounts.source_stats)
+        {
+            source_stats.set_inner(claimed_source_stats.into_inner());
         }
 
-        msg!(
-            "CLAIM SUCCESSFUL: User: {}, Amount: {}, Nonce used: {}, New nonce: {}, Timestamp: {}, Total claims: {}",
-            ctx.accounts.user.key(),
-            payload.claim_amount,
-            old_nonce,
-            user_data.nonce,
-            current_timestamp,
-            user_data.total_claims
-        );
-
-        Ok(())
+        Ok(receipt)
     }
 
     /// Burn tokens from user's account (admin authorized, user must sign)
@@ -714,6 +1704,9 @@ pub mod riyal_contract {
         amount: u64,
     ) -> Result<()> {
         let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::BURN);
         
         // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
@@ -779,6 +1772,12 @@ pub mod riyal_contract {
         // Burn tokens
         burn(cpi_ctx, amount)?;
 
+        // Track lifetime burns in the same instruction as the burn
+        let token_state = &mut ctx.accounts.token_state;
+        token_state.lifetime_burned = token_state.lifetime_burned
+            .checked_add(amount)
+            .ok_or(RiyalError::SupplyCounterOverflow)?;
+
         msg!(
             "BURN SUCCESSFUL: Admin: {}, User: {}, User Account: {}, Amount Burned: {}, Timestamp: {}",
             ctx.accounts.admin.key(),
@@ -791,6 +1790,195 @@ pub mod riyal_contract {
         Ok(())
     }
 
+    /// Reverse a user's most recent claim within reversal_window_seconds of it (admin only)
+    /// Burns up to the claimed amount from the user's account with the PDA as SPL delegate,
+    /// thawing and re-freezing around the burn when the account is frozen. The nonce is never
+    /// rolled back, so the reversed claim's signature cannot be replayed
+    /// The daily amount cap gets back the claim's gross amount (pro rata for partial reversals);
+    /// range_consumed and SourceStats are NOT restored - the range signature stays spent and the
+    /// campaign totals keep counting what was minted
+    pub fn reverse_claim(
+        ctx: Context<ReverseClaim>,
+        user: Pubkey,
+        nonce: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::BURN);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify the mint account matches the stored mint
+        require!(
+            ctx.accounts.mint.key() == token_state.token_mint,
+            RiyalError::InvalidTokenMint
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify the token account belongs to the user
+        require!(
+            ctx.accounts.user_token_account.mint == token_state.token_mint
+                && ctx.accounts.user_token_account.owner == user,
+            RiyalError::InvalidTokenAccount
+        );
+
+        // CRITICAL SECURITY CHECK 5: Reversals must be enabled and the claim still inside the window
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let user_data = &ctx.accounts.user_data;
+        require!(
+            token_state.reversal_window_seconds > 0
+                && user_data.last_claim_timestamp > 0
+                && current_timestamp <= user_data.last_claim_timestamp.saturating_add(token_state.reversal_window_seconds),
+            RiyalError::ReversalWindowClosed
+        );
+
+        // CRITICAL SECURITY CHECK 6: Only the latest, not yet reversed claim, and never more than it minted
+        require!(
+            user_data.nonce.checked_sub(1) == Some(nonce)
+                && amount > 0
+                && amount <= user_data.last_claim_amount,
+            RiyalError::InvalidReversal
+        );
+
+        // CRITICAL SECURITY CHECK 7: The user must have delegated at least `amount` to the PDA
+        require!(
+            ctx.accounts.user_token_account.delegate == COption::Some(token_state.key())
+                && ctx.accounts.user_token_account.delegated_amount >= amount,
+            RiyalError::ReversalNotDelegated
+        );
+
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Claimed balances are normally frozen - thaw for the burn and restore the freeze after
+        let was_frozen = ctx.accounts.user_token_account.is_frozen();
+        if was_frozen {
+            require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
+            let thaw_cpi_accounts = ThawAccount {
+                account: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+            thaw_account(CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds))?;
+        }
+
+        let burn_cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let burn_cpi_program = ctx.accounts.token_program.to_account_info();
+        burn(CpiContext::new_with_signer(burn_cpi_program, burn_cpi_accounts, signer_seeds), amount)?;
+
+        if was_frozen {
+            let freeze_cpi_accounts = FreezeAccount {
+                account: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let freeze_cpi_program = ctx.accounts.token_program.to_account_info();
+            freeze_account(CpiContext::new_with_signer(freeze_cpi_program, freeze_cpi_accounts, signer_seeds))?;
+        }
+
+        // Roll back the claim's effect on quotas; the claim can be reversed only once
+        // The daily cap counted the gross claim (burn tax and split included) - a partial
+        // reversal hands back the same share of it as of the burned user leg
+        let user_data = &mut ctx.accounts.user_data;
+        let daily_refund = (user_data.last_claim_daily_amount as u128)
+            .checked_mul(amount as u128)
+            .and_then(|scaled| scaled.checked_div(user_data.last_claim_amount as u128))
+            .and_then(|refund| u64::try_from(refund).ok())
+            .ok_or(RiyalError::InvalidReversal)?;
+        user_data.total_claims = user_data.total_claims.saturating_sub(1);
+        user_data.last_claim_amount = 0;
+        user_data.last_claim_daily_amount = 0;
+        if user_data.day_index == current_timestamp / 86400 {
+            user_data.amount_today = user_data.amount_today.saturating_sub(daily_refund);
+        }
+
+        // Track lifetime burns in the same instruction as the burn
+        let token_state = &mut ctx.accounts.token_state;
+        token_state.lifetime_burned = token_state.lifetime_burned
+            .checked_add(amount)
+            .ok_or(RiyalError::SupplyCounterOverflow)?;
+
+        emit!(ClaimReversed {
+            user,
+            nonce,
+            amount,
+            admin: ctx.accounts.admin.key(),
+            timestamp: current_timestamp,
+        });
+
+        msg!(
+            "CLAIM REVERSED: Admin: {}, User: {}, Nonce: {}, Amount Burned: {}, Timestamp: {}",
+            ctx.accounts.admin.key(),
+            user,
+            nonce,
+            amount,
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Set how long after a claim reverse_claim may undo it, 0 disables reversals (admin only)
+    pub fn set_reversal_window(
+        ctx: Context<SetReversalWindow>,
+        reversal_window_seconds: i64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Keep the undo window short
+        require!(
+            (0..=TokenState::MAX_REVERSAL_WINDOW_SECONDS).contains(&reversal_window_seconds),
+            RiyalError::InvalidReversalWindow
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_window = token_state.reversal_window_seconds;
+        token_state.reversal_window_seconds = reversal_window_seconds;
+
+        msg!(
+            "REVERSAL WINDOW UPDATED: Admin: {}, Window: {} → {} seconds",
+            ctx.accounts.admin.key(),
+            old_window,
+            reversal_window_seconds
+        );
+
+        Ok(())
+    }
+
     /// Enable token transfers (admin only, PERMANENT one-way operation)
     pub fn enable_transfers(ctx: Context<EnableTransfers>) -> Result<()> {
         let token_state = &mut ctx.accounts.token_state;
@@ -843,6 +2031,84 @@ pub mod riyal_contract {
         Ok(())
     }
 
+    /// Launch transfers: permanently enable them and thaw the treasury in one atomic step (admin only)
+    /// The treasury is frozen after any mint to it, which would otherwise leave it stuck at launch
+    pub fn launch_transfers(ctx: Context<LaunchTransfers>) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify transfers are not already permanently enabled
+        require!(
+            !token_state.transfers_permanently_enabled,
+            RiyalError::TransfersAlreadyPermanentlyEnabled
+        );
+
+        // CRITICAL SECURITY CHECK 5: Verify the treasury exists and is the one passed
+        require!(
+            token_state.treasury_account != Pubkey::default(),
+            RiyalError::TreasuryNotCreated
+        );
+        require!(
+            ctx.accounts.treasury_account.key() == token_state.treasury_account,
+            RiyalError::InvalidTreasuryAccount
+        );
+
+        let treasury_was_frozen = ctx.accounts.treasury_account.is_frozen();
+        if treasury_was_frozen {
+            require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
+            let seeds = &[
+                b"token_state".as_ref(),
+                &[token_state.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let thaw_cpi_accounts = ThawAccount {
+                account: ctx.accounts.treasury_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+            thaw_account(CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds))?;
+        }
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        // PERMANENT OPERATION: Enable transfers with immutable lock
+        let token_state = &mut ctx.accounts.token_state;
+        token_state.transfers_enabled = true;
+        token_state.transfers_permanently_enabled = true; // CANNOT BE CHANGED BACK
+        token_state.transfer_enable_timestamp = current_timestamp;
+
+        msg!(
+            "TRANSFERS LAUNCHED: Admin: {}, Token: {}, Treasury: {} (thawed: {}), Timestamp: {} - IRREVERSIBLE CHANGE",
+            ctx.accounts.admin.key(),
+            token_state.token_mint,
+            token_state.treasury_account,
+            treasury_was_frozen,
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
     /// Unfreeze user's token account (only callable after transfers are enabled)
     pub fn unfreeze_account(ctx: Context<UnfreezeAccount>) -> Result<()> {
         let token_state = &ctx.accounts.token_state;
@@ -871,6 +2137,9 @@ pub mod riyal_contract {
             RiyalError::InvalidTokenMint
         );
 
+        // Freeze/thaw CPIs below need the PDA as freeze authority (imported mints may lack it)
+        require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
         // CRITICAL SECURITY CHECK 5: Verify the token account is for the correct mint
         require!(
             ctx.accounts.user_token_account.mint == token_state.token_mint,
@@ -890,7 +2159,7 @@ pub mod riyal_contract {
         // Create PDA signer for minting
         let seeds = &[
             b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
+            &[ctx.accounts.token_state.bump],
         ];
         let _signer_seeds = &[&seeds[..]];
 
@@ -904,7 +2173,7 @@ pub mod riyal_contract {
         // Create PDA signer for unfreezing
         let unfreeze_seeds = &[
             b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
+            &[ctx.accounts.token_state.bump],
         ];
         let unfreeze_signer_seeds = &[&unfreeze_seeds[..]];
 
@@ -930,12 +2199,35 @@ pub mod riyal_contract {
         Ok(())
     }
 
+    /// Transfer the sender's entire balance (requires transfers to be enabled)
+    /// Reads the balance on-chain so the client never races a stale pre-read
+    /// Under require_transfer_approval the admin must sign the full balance as the amount
+    pub fn transfer_all(
+        ctx: Context<TransferTokens>,
+        approval_signature: Option<[u8; 64]>,
+    ) -> Result<()> {
+        let amount = ctx.accounts.from_token_account.amount;
+
+        require!(
+            amount > 0,
+            RiyalError::InsufficientBalance
+        );
+
+        transfer_tokens(ctx, amount, approval_signature)
+    }
+
     /// Transfer tokens between users (requires transfers to be enabled)
+    /// While require_transfer_approval is on, `approval_signature` must be the admin's Ed25519
+    /// signature over (from, to, amount, nonce) with the sender's current TransferNonce
     pub fn transfer_tokens(
         ctx: Context<TransferTokens>,
         amount: u64,
+        approval_signature: Option<[u8; 64]>,
     ) -> Result<()> {
         let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::TRANSFER);
         
         // CRITICAL SECURITY CHECK 1: Verify contract is initialized
         require!(
@@ -949,9 +2241,9 @@ pub mod riyal_contract {
             RiyalError::TokenMintNotCreated
         );
 
-        // CRITICAL SECURITY CHECK 3: Verify transfers are enabled
+        // CRITICAL SECURITY CHECK 3: Verify transfers are enabled, explicitly or by a passed schedule
         require!(
-            token_state.transfers_enabled,
+            token_state.transfers_active(Clock::get()?.unix_timestamp),
             RiyalError::TransfersNotEnabled
         );
 
@@ -972,6 +2264,12 @@ pub mod riyal_contract {
             RiyalError::InvalidTokenAccount
         );
 
+        // Reject degenerate self-transfers
+        require!(
+            ctx.accounts.from_token_account.key() != ctx.accounts.to_token_account.key(),
+            RiyalError::SelfTransfer
+        );
+
         // CRITICAL SECURITY CHECK 6: Verify amount is not zero
         require!(
             amount > 0,
@@ -990,6 +2288,63 @@ pub mod riyal_contract {
             RiyalError::UnauthorizedTransfer
         );
 
+        // Soft-locked owners can receive but not send
+        require!(
+            !SoftLock::is_locked(&ctx.accounts.from_soft_lock),
+            RiyalError::SoftLocked
+        );
+
+        // CRITICAL SECURITY CHECK 9: Reject transfers involving a blocked region
+        // User data PDAs are only required once at least one region is blocked
+        if token_state.has_blocked_regions() {
+            let from_user_data = ctx.accounts.from_user_data
+                .as_ref()
+                .ok_or(RiyalError::InvalidUserData)?;
+            let to_user_data = ctx.accounts.to_user_data
+                .as_ref()
+                .ok_or(RiyalError::InvalidUserData)?;
+
+            require!(
+                !token_state.is_region_blocked(UserData::region_of(from_user_data)?),
+                RiyalError::RegionBlocked
+            );
+            require!(
+                !token_state.is_region_blocked(UserData::region_of(to_user_data)?),
+                RiyalError::RegionBlocked
+            );
+        }
+
+        // CRITICAL SECURITY CHECK 10: Per-transfer admin approval, bound to the sender's nonce
+        if token_state.require_transfer_approval {
+            let approval_signature = approval_signature.ok_or(RiyalError::TransferApprovalRequired)?;
+            let instructions_sysvar = ctx.accounts.instructions
+                .as_ref()
+                .ok_or(RiyalError::TransferApprovalRequired)?;
+            let from_transfer_nonce = ctx.accounts.from_transfer_nonce
+                .as_mut()
+                .ok_or(RiyalError::TransferApprovalRequired)?;
+
+            let mut message_bytes = Vec::new();
+            message_bytes.extend_from_slice(TRANSFER_APPROVAL_MESSAGE_PREFIX);
+            message_bytes.extend_from_slice(&crate::ID.to_bytes());
+            message_bytes.extend_from_slice(&ctx.accounts.from_token_account.key().to_bytes());
+            message_bytes.extend_from_slice(&ctx.accounts.to_token_account.key().to_bytes());
+            message_bytes.extend_from_slice(&amount.to_le_bytes());
+            message_bytes.extend_from_slice(&from_transfer_nonce.nonce.to_le_bytes());
+            verify_admin_signature_only(
+                instructions_sysvar,
+                &message_bytes,
+                &approval_signature,
+                &token_state.admin,
+                token_state.strict_sig_position,
+            )?;
+
+            // Consume the nonce so the approval cannot be replayed
+            from_transfer_nonce.nonce = from_transfer_nonce.nonce
+                .checked_add(1)
+                .ok_or(RiyalError::NonceOverflow)?;
+        }
+
         // Get current timestamp for logging
         let clock = Clock::get()?;
         let current_timestamp = clock.unix_timestamp;
@@ -1006,14 +2361,268 @@ pub mod riyal_contract {
         // Transfer tokens
         transfer(cpi_ctx, amount)?;
 
-        msg!(
-            "TRANSFER SUCCESSFUL: From: {}, To: {}, Amount: {}, Timestamp: {}",
-            ctx.accounts.from_token_account.key(),
-            ctx.accounts.to_token_account.key(),
+        // Gated phase: re-freeze recipients that are not approved for free transfer, so
+        // tokens cannot be laundered to unapproved holders before transfers are permanent
+        let recipient_refrozen = token_state.refreeze_recipients
+            && !token_state.transfers_permanently_enabled
+            && !FreeTransfer::is_approved(&ctx.accounts.to_free_transfer);
+        if recipient_refrozen {
+            require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
+            let seeds = &[
+                b"token_state".as_ref(),
+                &[token_state.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let freeze_cpi_accounts = FreezeAccount {
+                account: ctx.accounts.to_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let freeze_cpi_program = ctx.accounts.token_program.to_account_info();
+            let freeze_cpi_ctx = CpiContext::new_with_signer(freeze_cpi_program, freeze_cpi_accounts, signer_seeds);
+
+            freeze_account(freeze_cpi_ctx)?;
+        }
+
+        // Opt-in velocity analytics: count transfers per sending owner
+        let mut transfer_count = 0;
+        if token_state.track_transfer_counts {
+            let from_transfer_stats = ctx.accounts.from_transfer_stats
+                .as_mut()
+                .ok_or(RiyalError::InvalidTransferStats)?;
+            from_transfer_stats.transfer_count = from_transfer_stats.transfer_count.saturating_add(1);
+            transfer_count = from_transfer_stats.transfer_count;
+        }
+
+        // Read post-transfer balances for the event
+        ctx.accounts.from_token_account.reload()?;
+        ctx.accounts.to_token_account.reload()?;
+
+        emit!(TransferEvent {
+            from: ctx.accounts.from_token_account.key(),
+            to: ctx.accounts.to_token_account.key(),
             amount,
-            current_timestamp
+            fee: 0,
+            from_balance_after: ctx.accounts.from_token_account.amount,
+            to_balance_after: ctx.accounts.to_token_account.amount,
+            transfer_count,
+            decimals: token_state.decimals,
+            timestamp: current_timestamp,
+        });
+
+        if !token_state.quiet_logging {
+            msg!(
+                "TRANSFER SUCCESSFUL: From: {}, To: {}, Amount: {}, Timestamp: {}",
+                ctx.accounts.from_token_account.key(),
+                ctx.accounts.to_token_account.key(),
+                amount,
+                current_timestamp
+            );
+            if recipient_refrozen {
+                msg!(
+                    "RECIPIENT REFROZEN: Account: {} - not approved for free transfer",
+                    ctx.accounts.to_token_account.key()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Thaw the sender's account and transfer in one instruction, optionally re-freezing the remainder
+    pub fn unfreeze_and_transfer(
+        ctx: Context<UnfreezeAndTransfer>,
+        amount: u64,
+        refreeze_remaining: bool,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::TRANSFER);
+        
+        // CRITICAL SECURITY CHECK 1: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify transfers are enabled
+        require!(
+            token_state.transfers_enabled,
+            RiyalError::TransfersNotEnabled
+        );
+
+        // CRITICAL SECURITY CHECK 4: Thawing is only allowed once transfers are permanently enabled
+        require!(
+            token_state.transfers_permanently_enabled,
+            RiyalError::TransfersNotPermanentlyEnabled
+        );
+
+        // Approved transfers must go through transfer_tokens, which verifies the admin signature
+        require!(
+            !token_state.require_transfer_approval,
+            RiyalError::TransferApprovalRequired
+        );
+
+        // Freeze/thaw CPIs below need the PDA as freeze authority (imported mints may lack it)
+        require_pda_freeze_authority(&ctx.accounts.mint, &token_state.key())?;
+
+        // CRITICAL SECURITY CHECK 5: Verify both token accounts are for the correct mint
+        require!(
+            ctx.accounts.from_token_account.mint == token_state.token_mint,
+            RiyalError::InvalidTokenAccount
+        );
+
+        require!(
+            ctx.accounts.to_token_account.mint == token_state.token_mint,
+            RiyalError::InvalidTokenAccount
+        );
+
+        // Reject degenerate self-transfers
+        require!(
+            ctx.accounts.from_token_account.key() != ctx.accounts.to_token_account.key(),
+            RiyalError::SelfTransfer
+        );
+
+        // CRITICAL SECURITY CHECK 6: Verify amount is not zero
+        require!(
+            amount > 0,
+            RiyalError::InvalidTransferAmount
+        );
+
+        // CRITICAL SECURITY CHECK 7: Verify sender has sufficient balance
+        require!(
+            ctx.accounts.from_token_account.amount >= amount,
+            RiyalError::InsufficientBalance
+        );
+
+        // CRITICAL SECURITY CHECK 8: Verify sender is the owner of the from account
+        require!(
+            ctx.accounts.from_token_account.owner == ctx.accounts.from_authority.key(),
+            RiyalError::UnauthorizedTransfer
+        );
+
+        // Soft-locked owners can receive but not send
+        require!(
+            !SoftLock::is_locked(&ctx.accounts.from_soft_lock),
+            RiyalError::SoftLocked
         );
 
+        // CRITICAL SECURITY CHECK 9: Reject transfers involving a blocked region
+        if token_state.has_blocked_regions() {
+            let from_user_data = ctx.accounts.from_user_data
+                .as_ref()
+                .ok_or(RiyalError::InvalidUserData)?;
+            let to_user_data = ctx.accounts.to_user_data
+                .as_ref()
+                .ok_or(RiyalError::InvalidUserData)?;
+
+            require!(
+                !token_state.is_region_blocked(UserData::region_of(from_user_data)?),
+                RiyalError::RegionBlocked
+            );
+            require!(
+                !token_state.is_region_blocked(UserData::region_of(to_user_data)?),
+                RiyalError::RegionBlocked
+            );
+        }
+
+        // Get current timestamp for logging
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        // Create PDA signer for thaw/freeze
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[ctx.accounts.token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Thaw the source account if it is still frozen from claims
+        if ctx.accounts.from_token_account.is_frozen() {
+            let thaw_cpi_accounts = ThawAccount {
+                account: ctx.accounts.from_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+            let thaw_cpi_ctx = CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds);
+
+            thaw_account(thaw_cpi_ctx)?;
+        }
+
+        // Transfer tokens with the owner's signature
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.from_token_account.to_account_info(),
+            to: ctx.accounts.to_token_account.to_account_info(),
+            authority: ctx.accounts.from_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        // Optionally keep any remaining balance locked
+        let remaining = ctx.accounts.from_token_account.amount - amount;
+        let refrozen = refreeze_remaining && remaining > 0;
+        if refrozen {
+            let freeze_cpi_accounts = FreezeAccount {
+                account: ctx.accounts.from_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let freeze_cpi_program = ctx.accounts.token_program.to_account_info();
+            let freeze_cpi_ctx = CpiContext::new_with_signer(freeze_cpi_program, freeze_cpi_accounts, signer_seeds);
+
+            freeze_account(freeze_cpi_ctx)?;
+        }
+
+        // Opt-in velocity analytics: count transfers per sending owner
+        let mut transfer_count = 0;
+        if token_state.track_transfer_counts {
+            let from_transfer_stats = ctx.accounts.from_transfer_stats
+                .as_mut()
+                .ok_or(RiyalError::InvalidTransferStats)?;
+            from_transfer_stats.transfer_count = from_transfer_stats.transfer_count.saturating_add(1);
+            transfer_count = from_transfer_stats.transfer_count;
+        }
+
+        // Read post-transfer balances for the event
+        ctx.accounts.from_token_account.reload()?;
+        ctx.accounts.to_token_account.reload()?;
+
+        emit!(TransferEvent {
+            from: ctx.accounts.from_token_account.key(),
+            to: ctx.accounts.to_token_account.key(),
+            amount,
+            fee: 0,
+            from_balance_after: ctx.accounts.from_token_account.amount,
+            to_balance_after: ctx.accounts.to_token_account.amount,
+            transfer_count,
+            decimals: token_state.decimals,
+            timestamp: current_timestamp,
+        });
+
+        if !token_state.quiet_logging {
+            msg!(
+                "UNFREEZE AND TRANSFER SUCCESSFUL: From: {}, To: {}, Amount: {}, Remaining: {}, Re-frozen: {}, Timestamp: {}",
+                ctx.accounts.from_token_account.key(),
+                ctx.accounts.to_token_account.key(),
+                amount,
+                remaining,
+                refrozen,
+                current_timestamp
+            );
+        }
+
         Ok(())
     }
 
@@ -1037,6 +2646,9 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
         // Validate claim period (must be reasonable)
         require!(
             claim_period_seconds >= 3600, // Minimum 1 hour
@@ -1072,17 +2684,18 @@ pub mod riyal_contract {
         Ok(())
     }
 
-    /// Set upgrade authority (current upgrade authority only)
-    pub fn set_upgrade_authority(
-        ctx: Context<SetUpgradeAuthority>,
-        new_upgrade_authority: Option<Pubkey>,
+    /// Update slot-based time-lock settings (admin only)
+    pub fn update_slot_time_lock(
+        ctx: Context<UpdateSlotTimeLock>,
+        claim_period_slots: u64,
+        use_slot_timelock: bool,
     ) -> Result<()> {
         let token_state = &mut ctx.accounts.token_state;
         
-        // CRITICAL SECURITY CHECK 1: Verify current upgrade authority is calling this function
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
-            ctx.accounts.current_upgrade_authority.key() == token_state.upgrade_authority,
-            RiyalError::UnauthorizedUpgradeAuthority
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
         );
 
         // CRITICAL SECURITY CHECK 2: Verify contract is initialized
@@ -1091,50 +2704,50 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
-        // CRITICAL SECURITY CHECK 3: Verify contract is upgradeable
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        // Validate claim period in slots (~400ms per slot)
+        // The minimum only matters while slot mode is on, so it can be switched off with a 0 period
         require!(
-            token_state.upgradeable,
-            RiyalError::ContractNotUpgradeable
+            !use_slot_timelock || claim_period_slots >= 75, // Minimum ~30 seconds
+            RiyalError::InvalidClaimPeriod
+        );
+        
+        require!(
+            claim_period_slots <= 78_840_000, // Maximum ~1 year
+            RiyalError::InvalidClaimPeriod
         );
 
-        // Get current timestamp for logging
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
-
-        let old_authority = token_state.upgrade_authority;
+        let old_period = token_state.claim_period_slots;
+        let old_enabled = token_state.use_slot_timelock;
+        
+        token_state.claim_period_slots = claim_period_slots;
+        token_state.use_slot_timelock = use_slot_timelock;
 
-        match new_upgrade_authority {
-            Some(new_auth) => {
-                token_state.upgrade_authority = new_auth;
-                msg!(
-                    "UPGRADE AUTHORITY CHANGED: {} → {}, Timestamp: {}",
-                    old_authority,
-                    new_auth,
-                    current_timestamp
-                );
-            }
-            None => {
-                // Setting to None makes contract non-upgradeable permanently
-                token_state.upgrade_authority = Pubkey::default();
-                token_state.upgradeable = false;
-                msg!(
-                    "UPGRADE AUTHORITY REMOVED: Contract is now IMMUTABLE, Timestamp: {}",
-                    current_timestamp
-                );
-            }
-        }
+        msg!(
+            "SLOT TIME-LOCK UPDATED: Admin: {}, Period: {} → {} slots, Slot mode: {} → {}",
+            ctx.accounts.admin.key(),
+            old_period,
+            claim_period_slots,
+            old_enabled,
+            use_slot_timelock
+        );
 
         Ok(())
     }
 
-    /// Validate upgrade authorization (called before upgrades)
-    pub fn validate_upgrade(ctx: Context<ValidateUpgrade>) -> Result<()> {
-        let token_state = &ctx.accounts.token_state;
+    /// Require the Ed25519 verify instruction to immediately precede the claim (admin only)
+    pub fn set_strict_sig_position(
+        ctx: Context<SetStrictSigPosition>,
+        strict_sig_position: bool,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
         
-        // CRITICAL SECURITY CHECK 1: Verify upgrade authority is calling this function
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
-            ctx.accounts.upgrade_authority.key() == token_state.upgrade_authority,
-            RiyalError::UnauthorizedUpgradeAuthority
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
         );
 
         // CRITICAL SECURITY CHECK 2: Verify contract is initialized
@@ -1143,34 +2756,28 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
-        // CRITICAL SECURITY CHECK 3: Verify contract is upgradeable
-        require!(
-            token_state.upgradeable,
-            RiyalError::ContractNotUpgradeable
-        );
-
-        // CRITICAL SECURITY CHECK 4: Verify program data account
-        require!(
-            ctx.accounts.program_data.key() != Pubkey::default(),
-            RiyalError::InvalidProgramData
-        );
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
 
-        // Get current timestamp for logging
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
+        let old_strict = token_state.strict_sig_position;
+        token_state.strict_sig_position = strict_sig_position;
 
         msg!(
-            "UPGRADE VALIDATED: Authority: {}, Program: {}, Timestamp: {} - UPGRADE AUTHORIZED",
-            ctx.accounts.upgrade_authority.key(),
-            ctx.program_id,
-            current_timestamp
+            "STRICT SIGNATURE POSITION UPDATED: Admin: {}, Strict: {} → {}",
+            ctx.accounts.admin.key(),
+            old_strict,
+            strict_sig_position
         );
 
         Ok(())
     }
 
-    /// Create contract treasury account (admin only)
-    pub fn create_treasury(ctx: Context<CreateTreasury>) -> Result<()> {
+    /// Configure oracle pricing for USD-denominated claim amounts (admin only)
+    pub fn set_oracle_pricing(
+        ctx: Context<SetOraclePricing>,
+        price_oracle: Pubkey,
+        use_oracle_pricing: bool,
+    ) -> Result<()> {
         let token_state = &mut ctx.accounts.token_state;
         
         // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
@@ -1185,41 +2792,35 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
-        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
-        require!(
-            token_state.token_mint != Pubkey::default(),
-            RiyalError::TokenMintNotCreated
-        );
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
 
-        // CRITICAL SECURITY CHECK 4: Verify treasury not already created
+        // CRITICAL SECURITY CHECK 3: Oracle pricing needs a configured price feed
         require!(
-            token_state.treasury_account == Pubkey::default(),
-            RiyalError::TreasuryAlreadyCreated
+            !use_oracle_pricing || price_oracle != Pubkey::default(),
+            RiyalError::InvalidOracleAccount
         );
 
-        // Store treasury account
-        token_state.treasury_account = ctx.accounts.treasury_account.key();
-
-        // Get current timestamp for logging
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
+        token_state.price_oracle = price_oracle;
+        token_state.use_oracle_pricing = use_oracle_pricing;
 
         msg!(
-            "TREASURY CREATED: Admin: {}, Treasury Account: {}, Timestamp: {}",
+            "ORACLE PRICING UPDATED: Admin: {}, Oracle: {}, Enabled: {}",
             ctx.accounts.admin.key(),
-            ctx.accounts.treasury_account.key(),
-            current_timestamp
+            price_oracle,
+            use_oracle_pricing
         );
 
         Ok(())
     }
 
-    /// Mint tokens to contract treasury (admin only)
-    pub fn mint_to_treasury(
-        ctx: Context<MintToTreasury>,
-        amount: u64,
+    /// Block or unblock a jurisdiction tag for claims and transfers (admin only)
+    pub fn set_region_blocked(
+        ctx: Context<SetRegionBlocked>,
+        region: u16,
+        blocked: bool,
     ) -> Result<()> {
-        let token_state = &ctx.accounts.token_state;
+        let token_state = &mut ctx.accounts.token_state;
         
         // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
@@ -1233,70 +2834,144 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
-        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
-        require!(
-            token_state.token_mint != Pubkey::default(),
-            RiyalError::TokenMintNotCreated
-        );
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
 
-        // CRITICAL SECURITY CHECK 4: Verify treasury has been created
+        // CRITICAL SECURITY CHECK 3: Region 0 means untagged and cannot be blocked
         require!(
-            token_state.treasury_account != Pubkey::default(),
-            RiyalError::TreasuryNotCreated
+            region > 0 && region < TokenState::MAX_REGIONS,
+            RiyalError::InvalidRegion
         );
 
-        // CRITICAL SECURITY CHECK 5: Verify treasury account matches stored account
-        require!(
-            ctx.accounts.treasury_account.key() == token_state.treasury_account,
-            RiyalError::InvalidTreasuryAccount
-        );
+        let byte = (region / 8) as usize;
+        let bit = 1u8 << (region % 8);
+        if blocked {
+            token_state.blocked_regions[byte] |= bit;
+        } else {
+            token_state.blocked_regions[byte] &= !bit;
+        }
 
-        // CRITICAL SECURITY CHECK 6: Verify amount is not zero
+        msg!(
+            "REGION {} {} by admin: {}",
+            region,
+            if blocked { "BLOCKED" } else { "UNBLOCKED" },
+            ctx.accounts.admin.key()
+        );
+
+        Ok(())
+    }
+
+    /// Create the caller's TransferStats PDA used for transfer count tracking (owner pays rent)
+    pub fn initialize_transfer_stats(ctx: Context<InitializeTransferStats>) -> Result<()> {
+        let transfer_stats = &mut ctx.accounts.transfer_stats;
+        transfer_stats.owner = ctx.accounts.owner.key();
+        transfer_stats.transfer_count = 0;
+        transfer_stats.bump = ctx.bumps.transfer_stats;
+
+        msg!(
+            "Transfer stats initialized for owner: {}",
+            transfer_stats.owner
+        );
+
+        Ok(())
+    }
+
+    /// Number of program transfers sent by `owner` while tracking was enabled
+    pub fn get_transfer_count(ctx: Context<GetTransferCount>, _owner: Pubkey) -> Result<u64> {
+        Ok(ctx.accounts.transfer_stats.transfer_count)
+    }
+
+    /// Create the caller's TransferNonce PDA, required to send transfers under require_transfer_approval (owner pays rent)
+    pub fn initialize_transfer_nonce(ctx: Context<InitializeTransferNonce>) -> Result<()> {
+        let transfer_nonce = &mut ctx.accounts.transfer_nonce;
+        transfer_nonce.owner = ctx.accounts.owner.key();
+        transfer_nonce.nonce = 0;
+        transfer_nonce.bump = ctx.bumps.transfer_nonce;
+
+        msg!(
+            "Transfer nonce initialized for owner: {}",
+            transfer_nonce.owner
+        );
+
+        Ok(())
+    }
+
+    /// Create the SourceStats PDA for a campaign tag, so claims signed with it can be counted (admin only)
+    /// Re-creating a closed tag restarts its totals and re-validates its outstanding signatures
+    pub fn initialize_source_stats(ctx: Context<InitializeSourceStats>, source: u16) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
-            amount > 0,
-            RiyalError::InvalidMintAmount
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
         );
 
-        // Create PDA signer for minting
-        let seeds = &[
-            b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        // CRITICAL SECURITY CHECK 2: Tag 0 means untagged and is never counted
+        require!(
+            source != 0,
+            RiyalError::InvalidSourceStats
+        );
 
-        // Create CPI context for minting to treasury
-        let cpi_accounts = MintTo {
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.treasury_account.to_account_info(),
-            authority: ctx.accounts.token_state.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        let source_stats = &mut ctx.accounts.source_stats;
+        source_stats.source = source;
+        source_stats.claim_count = 0;
+        source_stats.total_amount = 0;
+        source_stats.bump = ctx.bumps.source_stats;
 
-        // Mint tokens to treasury
-        mint_to(cpi_ctx, amount)?;
+        msg!(
+            "Source stats initialized for source: {} by admin: {}",
+            source,
+            ctx.accounts.admin.key()
+        );
 
-        // Get current timestamp for logging
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
+        Ok(())
+    }
+
+    /// Claim count and total minted amount attributed to a campaign tag
+    pub fn get_source_stats(ctx: Context<GetSourceStats>, _source: u16) -> Result<SourceStats> {
+        Ok(ctx.accounts.source_stats.clone().into_inner())
+    }
+
+    /// End a campaign: close its SourceStats PDA and refund the rent to the admin (admin only)
+    /// Outstanding signed claims tagged with `source` are rejected (INVALID_SOURCE_STATS) only while
+    /// the PDA stays closed - initialize_source_stats can re-create it with zeroed totals, after which
+    /// the campaign's unexpired signatures are honored again. Never reuse a closed tag for a new campaign
+    pub fn close_source_stats(ctx: Context<CloseSourceStats>, source: u16) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        let source_stats = &ctx.accounts.source_stats;
 
         msg!(
-            "MINTED TO TREASURY: Admin: {}, Amount: {}, Treasury: {}, Timestamp: {}",
+            "SOURCE STATS CLOSED: Admin: {}, Source: {}, Final claims: {}, Final amount: {}",
             ctx.accounts.admin.key(),
-            amount,
-            ctx.accounts.treasury_account.key(),
-            current_timestamp
+            source,
+            source_stats.claim_count,
+            source_stats.total_amount
         );
 
         Ok(())
     }
 
-    /// Burn tokens from contract treasury (admin only)
-    pub fn burn_from_treasury(
-        ctx: Context<BurnFromTreasury>,
-        amount: u64,
+    /// Turn per-owner transfer count tracking on or off (admin only)
+    /// While on, transfer_tokens and unfreeze_and_transfer require the sender's TransferStats PDA
+    pub fn set_track_transfer_counts(
+        ctx: Context<SetTrackTransferCounts>,
+        track_transfer_counts: bool,
     ) -> Result<()> {
-        let token_state = &ctx.accounts.token_state;
+        let token_state = &mut ctx.accounts.token_state;
         
         // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
@@ -1310,97 +2985,5139 @@ pub mod riyal_contract {
             RiyalError::ContractNotInitialized
         );
 
-        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_track = token_state.track_transfer_counts;
+        token_state.track_transfer_counts = track_transfer_counts;
+
+        msg!(
+            "TRANSFER COUNT TRACKING UPDATED: Admin: {}, Tracking: {} → {}",
+            ctx.accounts.admin.key(),
+            old_track,
+            track_transfer_counts
+        );
+
+        Ok(())
+    }
+
+    /// Turn per-transfer admin approval on or off (admin only)
+    /// While on, transfer_tokens requires an admin signature over (from, to, amount, nonce)
+    /// and unfreeze_and_transfer is rejected
+    pub fn set_require_transfer_approval(
+        ctx: Context<SetRequireTransferApproval>,
+        require_transfer_approval: bool,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
-            token_state.token_mint != Pubkey::default(),
-            RiyalError::TokenMintNotCreated
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
         );
 
-        // CRITICAL SECURITY CHECK 4: Verify treasury has been created
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
         require!(
-            token_state.treasury_account != Pubkey::default(),
-            RiyalError::TreasuryNotCreated
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
         );
 
-        // CRITICAL SECURITY CHECK 5: Verify treasury account matches stored account
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_require = token_state.require_transfer_approval;
+        token_state.require_transfer_approval = require_transfer_approval;
+
+        msg!(
+            "TRANSFER APPROVAL UPDATED: Admin: {}, Required: {} → {}",
+            ctx.accounts.admin.key(),
+            old_require,
+            require_transfer_approval
+        );
+
+        Ok(())
+    }
+
+    /// Soft-lock a user: they can still receive tokens but program transfers from them are rejected (admin only)
+    /// SPL freeze blocks both directions; the soft lock is enforced by transfer_tokens and
+    /// unfreeze_and_transfer, which require the owner's SoftLock PDA to be absent
+    /// LIMITATION: it is not enforced against direct SPL Token transfers - an owner whose account
+    /// is thawed can still send via the token program; freeze the account to block that path
+    pub fn soft_lock(ctx: Context<SoftLockUser>, user: Pubkey) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
-            ctx.accounts.treasury_account.key() == token_state.treasury_account,
-            RiyalError::InvalidTreasuryAccount
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
         );
 
-        // CRITICAL SECURITY CHECK 6: Verify amount is not zero
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
         require!(
-            amount > 0,
-            RiyalError::InvalidBurnAmount
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
         );
 
-        // CRITICAL SECURITY CHECK 7: Verify treasury has sufficient balance
+        let clock = Clock::get()?;
+        let soft_lock = &mut ctx.accounts.soft_lock;
+        soft_lock.owner = user;
+        soft_lock.locked_at = clock.unix_timestamp;
+        soft_lock.bump = ctx.bumps.soft_lock;
+
+        msg!(
+            "USER SOFT-LOCKED: User: {}, Admin: {}, Timestamp: {}",
+            user,
+            ctx.accounts.admin.key(),
+            clock.unix_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Remove a user's soft lock, closing the SoftLock PDA to the admin (admin only)
+    pub fn soft_unlock(ctx: Context<SoftUnlockUser>, user: Pubkey) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
         require!(
-            ctx.accounts.treasury_account.amount >= amount,
-            RiyalError::InsufficientTreasuryBalance
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
         );
 
-        // Create PDA signer for burning from treasury
-        let seeds = &[
-            b"token_state".as_ref(),
-            &[ctx.bumps.token_state],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
 
-        // Create CPI context for burning from treasury
-        let cpi_accounts = Burn {
-            mint: ctx.accounts.mint.to_account_info(),
-            from: ctx.accounts.treasury_account.to_account_info(),
-            authority: ctx.accounts.token_state.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        msg!(
+            "USER SOFT-UNLOCKED: User: {}, Admin: {}",
+            user,
+            ctx.accounts.admin.key()
+        );
 
-        // Burn tokens from treasury
-        burn(cpi_ctx, amount)?;
+        Ok(())
+    }
+
+    /// Approve a user for free transfer: tokens they receive via transfer_tokens stay thawed
+    /// during the gated phase while refreeze_recipients is on (admin only)
+    pub fn approve_free_transfer(ctx: Context<ApproveFreeTransfer>, user: Pubkey) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
 
-        // Get current timestamp for logging
         let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
+        let free_transfer = &mut ctx.accounts.free_transfer;
+        free_transfer.owner = user;
+        free_transfer.approved_at = clock.unix_timestamp;
+        free_transfer.bump = ctx.bumps.free_transfer;
 
         msg!(
-            "BURNED FROM TREASURY: Admin: {}, Amount: {}, Treasury: {}, Timestamp: {}, Remaining: {}",
+            "FREE TRANSFER APPROVED: User: {}, Admin: {}, Timestamp: {}",
+            user,
             ctx.accounts.admin.key(),
-            amount,
-            ctx.accounts.treasury_account.key(),
-            current_timestamp,
-            ctx.accounts.treasury_account.amount.saturating_sub(amount)
+            clock.unix_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Revoke a user's free-transfer approval, closing the FreeTransfer PDA to the admin (admin only)
+    pub fn revoke_free_transfer(ctx: Context<RevokeFreeTransfer>, user: Pubkey) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        msg!(
+            "FREE TRANSFER REVOKED: User: {}, Admin: {}",
+            user,
+            ctx.accounts.admin.key()
+        );
+
+        Ok(())
+    }
+
+    /// Turn recipient re-freezing on or off for the gated transfer phase (admin only)
+    /// While on and transfers are not yet permanent, transfer_tokens freezes the destination
+    /// account unless its owner holds a FreeTransfer approval
+    pub fn set_refreeze_recipients(
+        ctx: Context<SetRefreezeRecipients>,
+        refreeze_recipients: bool,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_refreeze = token_state.refreeze_recipients;
+        token_state.refreeze_recipients = refreeze_recipients;
+
+        msg!(
+            "RECIPIENT REFREEZE UPDATED: Admin: {}, Refreeze: {} → {}",
+            ctx.accounts.admin.key(),
+            old_refreeze,
+            refreeze_recipients
         );
 
         Ok(())
     }
+
+    /// Tag a user with a jurisdiction region (admin only)
+    pub fn set_user_region(
+        ctx: Context<SetUserRegion>,
+        user: Pubkey,
+        region: u16,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify region fits the blocked_regions bitmap
+        require!(
+            region < TokenState::MAX_REGIONS,
+            RiyalError::InvalidRegion
+        );
+
+        let user_data = &mut ctx.accounts.user_data;
+        let old_region = user_data.region;
+        user_data.region = region;
+
+        msg!(
+            "USER REGION UPDATED: User: {}, Region: {} → {}, Admin: {}",
+            user,
+            old_region,
+            region,
+            ctx.accounts.admin.key()
+        );
+
+        Ok(())
+    }
+
+    /// TEST ONLY: overwrite a user's last claim time to fast-forward cooldowns (admin only)
+    /// Compiled out of production builds - requires the `test-helpers` feature
+    #[cfg(feature = "test-helpers")]
+    pub fn __test_set_last_claim(
+        ctx: Context<TestSetLastClaim>,
+        user: Pubkey,
+        ts: i64,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        let user_data = &mut ctx.accounts.user_data;
+        user_data.last_claim_timestamp = ts;
+        user_data.next_allowed_claim_time = ts
+            .saturating_add(token_state.effective_claim_period(user_data.total_claims));
+
+        msg!(
+            "TEST LAST CLAIM SET: User: {}, Last claim: {}, Next allowed: {}",
+            user,
+            user_data.last_claim_timestamp,
+            user_data.next_allowed_claim_time
+        );
+
+        Ok(())
+    }
+
+    /// Set the primary mint supply cap and the portion reserved for the treasury (admin only)
+    pub fn set_supply_cap(
+        ctx: Context<SetSupplyCap>,
+        max_supply: u64,
+        reserved_for_treasury: u64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        // CRITICAL SECURITY CHECK 3: A cap can't be below what already exists, and the reserve must fit in it
+        if max_supply > 0 {
+            require!(
+                max_supply >= ctx.accounts.mint.supply,
+                RiyalError::InvalidSupplyCap
+            );
+            require!(
+                reserved_for_treasury <= max_supply,
+                RiyalError::InvalidSupplyCap
+            );
+        } else {
+            require!(
+                reserved_for_treasury == 0,
+                RiyalError::InvalidSupplyCap
+            );
+        }
+
+        token_state.max_supply = max_supply;
+        token_state.reserved_for_treasury = reserved_for_treasury;
+
+        msg!(
+            "SUPPLY CAP UPDATED: Admin: {}, Max Supply: {}, Reserved for Treasury: {}, Current Supply: {}",
+            ctx.accounts.admin.key(),
+            max_supply,
+            reserved_for_treasury,
+            ctx.accounts.mint.supply
+        );
+
+        Ok(())
+    }
+
+    /// Set the lifetime claim quota per user, 0 removes the quota (admin only)
+    pub fn set_max_claims_per_user(
+        ctx: Context<SetMaxClaimsPerUser>,
+        max_claims_per_user: u64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_max_claims = token_state.max_claims_per_user;
+        token_state.max_claims_per_user = max_claims_per_user;
+
+        msg!(
+            "CLAIM QUOTA UPDATED: Admin: {}, Max claims per user: {} → {}",
+            ctx.accounts.admin.key(),
+            old_max_claims,
+            max_claims_per_user
+        );
+
+        Ok(())
+    }
+
+    /// Set the anomaly guard multiplier, 0 disables the guard (admin only)
+    /// Claims above `anomaly_multiplier` times the EWMA of past claim amounts are rejected
+    /// unless the signed payload sets anomaly_override; the average is updated on every claim
+    pub fn set_anomaly_multiplier(
+        ctx: Context<SetAnomalyMultiplier>,
+        anomaly_multiplier: u16,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: A multiplier of 1 would reject every above-average claim
+        require!(
+            anomaly_multiplier == 0 || anomaly_multiplier >= 2,
+            RiyalError::InvalidAnomalyMultiplier
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_multiplier = token_state.anomaly_multiplier;
+        token_state.anomaly_multiplier = anomaly_multiplier;
+
+        msg!(
+            "ANOMALY MULTIPLIER UPDATED: Admin: {}, Multiplier: {} → {}, Current average: {}",
+            ctx.accounts.admin.key(),
+            old_multiplier,
+            anomaly_multiplier,
+            token_state.claim_amount_ewma
+        );
+
+        Ok(())
+    }
+
+    /// Set the burn-on-claim tax in basis points, 0 disables it (admin only)
+    /// Users receive the authorized amount minus the tax; the tax is never minted but is counted in
+    /// both lifetime_minted and lifetime_burned
+    pub fn set_claim_burn_bps(
+        ctx: Context<SetClaimBurnBps>,
+        claim_burn_bps: u16,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Claims must always deliver something to the user
+        require!(
+            claim_burn_bps < 10000,
+            RiyalError::InvalidClaimBurnBps
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_bps = token_state.claim_burn_bps;
+        token_state.claim_burn_bps = claim_burn_bps;
+
+        msg!(
+            "CLAIM BURN UPDATED: Admin: {}, Burn: {} → {} bps",
+            ctx.accounts.admin.key(),
+            old_bps,
+            claim_burn_bps
+        );
+
+        Ok(())
+    }
+
+    /// Set the global claim window `[open, close)`, 0 leaves that side unbounded (admin only)
+    pub fn set_claim_window(
+        ctx: Context<SetClaimWindow>,
+        claim_window_open: i64,
+        claim_window_close: i64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify the window is well formed
+        require!(
+            claim_window_open >= 0
+                && claim_window_close >= 0
+                && (claim_window_open == 0 || claim_window_close == 0 || claim_window_open < claim_window_close),
+            RiyalError::InvalidClaimWindow
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_open = token_state.claim_window_open;
+        let old_close = token_state.claim_window_close;
+        token_state.claim_window_open = claim_window_open;
+        token_state.claim_window_close = claim_window_close;
+
+        msg!(
+            "CLAIM WINDOW UPDATED: Admin: {}, Open: {} → {}, Close: {} → {}",
+            ctx.accounts.admin.key(),
+            old_open,
+            claim_window_open,
+            old_close,
+            claim_window_close
+        );
+
+        Ok(())
+    }
+
+    /// Restrict every user to a single lifetime claim, or lift the restriction (admin only)
+    pub fn set_single_claim_mode(
+        ctx: Context<SetSingleClaimMode>,
+        single_claim_mode: bool,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_single_claim_mode = token_state.single_claim_mode;
+        token_state.single_claim_mode = single_claim_mode;
+
+        msg!(
+            "SINGLE CLAIM MODE UPDATED: Admin: {}, Single claim: {} → {}",
+            ctx.accounts.admin.key(),
+            old_single_claim_mode,
+            single_claim_mode
+        );
+
+        Ok(())
+    }
+
+    /// Set how far in the future a signed claim expiry may lie, 0 removes the limit (admin only)
+    pub fn set_max_future_expiry(
+        ctx: Context<SetMaxFutureExpiry>,
+        max_future_expiry_seconds: i64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Negative windows would reject every claim
+        require!(
+            max_future_expiry_seconds >= 0,
+            RiyalError::InvalidMaxFutureExpiry
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_max_future_expiry = token_state.max_future_expiry_seconds;
+        token_state.max_future_expiry_seconds = max_future_expiry_seconds;
+
+        msg!(
+            "MAX FUTURE EXPIRY UPDATED: Admin: {}, Max future expiry: {} → {} seconds",
+            ctx.accounts.admin.key(),
+            old_max_future_expiry,
+            max_future_expiry_seconds
+        );
+
+        Ok(())
+    }
+
+    /// Deny minting to a wallet (token account owner) or a specific token account (admin only)
+    pub fn deny_mint_destination(
+        ctx: Context<UpdateMintDenylist>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Idempotent - an already denied destination is left as is
+        if !token_state.is_mint_denied(&destination) {
+            require!(
+                token_state.mint_denylist.len() < TokenState::MAX_MINT_DENYLIST,
+                RiyalError::MintDenylistFull
+            );
+            token_state.mint_denylist.push(destination);
+        }
+
+        msg!(
+            "MINT DESTINATION DENIED: Admin: {}, Destination: {}, Denylist size: {}",
+            ctx.accounts.admin.key(),
+            destination,
+            token_state.mint_denylist.len()
+        );
+
+        Ok(())
+    }
+
+    /// Remove a destination from the mint denylist (admin only)
+    pub fn allow_mint_destination(
+        ctx: Context<UpdateMintDenylist>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes (lifting a denial loosens policy)
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        token_state.mint_denylist.retain(|denied| *denied != destination);
+
+        msg!(
+            "MINT DESTINATION ALLOWED: Admin: {}, Destination: {}, Denylist size: {}",
+            ctx.accounts.admin.key(),
+            destination,
+            token_state.mint_denylist.len()
+        );
+
+        Ok(())
+    }
+
+    /// Set the minimum SOL balance (in lamports) a claimer's wallet must hold, 0 disables it (admin only)
+    /// Only a mild deterrent - funding throwaway wallets with a little SOL defeats it trivially
+    pub fn set_min_claimer_sol(
+        ctx: Context<SetMinClaimerSol>,
+        min_claimer_sol: u64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_min_sol = token_state.min_claimer_sol;
+        token_state.min_claimer_sol = min_claimer_sol;
+
+        msg!(
+            "MIN CLAIMER SOL UPDATED: Admin: {}, Min lamports: {} → {}",
+            ctx.accounts.admin.key(),
+            old_min_sol,
+            min_claimer_sol
+        );
+
+        Ok(())
+    }
+
+    /// Pause or resume the admin-signed claim path without touching anything else (admin only)
+    pub fn set_signature_claims_paused(
+        ctx: Context<SetSignatureClaimsPaused>,
+        paused: bool,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        let old_paused = token_state.signature_claims_paused;
+        token_state.signature_claims_paused = paused;
+
+        msg!(
+            "SIGNATURE CLAIMS PAUSE UPDATED: Admin: {}, Paused: {} → {}",
+            ctx.accounts.admin.key(),
+            old_paused,
+            paused
+        );
+
+        Ok(())
+    }
+
+    /// Apply several time-lock and cap changes atomically (admin only)
+    /// Every provided field is validated against the resulting config as a whole before any is written
+    pub fn update_config(ctx: Context<UpdateConfig>, config: ConfigUpdate) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        token_state.apply_config_update(&config, ctx.accounts.mint.supply)?;
+
+        msg!(
+            "CONFIG UPDATED: Admin: {}, {}",
+            ctx.accounts.admin.key(),
+            token_state.config_summary()
+        );
+
+        Ok(())
+    }
+
+    /// Apply a config change the admin signed offline - any relayer may submit it
+    /// Requires an Ed25519 verify instruction over "RIYAL_CONFIG_V1" | program_id | borsh(signed_config)
+    /// signed by the admin; the nonce must equal config_nonce and is consumed on success
+    pub fn apply_signed_config(
+        ctx: Context<ApplySignedConfig>,
+        signed_config: SignedConfig,
+        admin_signature: [u8; 64],
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+
+        // CRITICAL SECURITY CHECK 1: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 2: Reject replayed or out-of-order signed configs
+        require!(
+            signed_config.nonce == token_state.config_nonce,
+            RiyalError::InvalidNonce
+        );
+
+        // CRITICAL SECURITY CHECK 3: Reject expired authorizations
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            current_timestamp <= signed_config.expiry_time,
+            RiyalError::SignedConfigExpired
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify the admin (never the claim signer) signed this exact config
+        let config_bytes = signed_config.try_to_vec().map_err(|_| RiyalError::InvalidClaimPayload)?;
+        let mut message_bytes = Vec::new();
+        message_bytes.extend_from_slice(CONFIG_MESSAGE_PREFIX);
+        message_bytes.extend_from_slice(&crate::ID.to_bytes());
+        message_bytes.extend_from_slice(&config_bytes);
+        verify_admin_signature_only(
+            &ctx.accounts.instructions,
+            &message_bytes,
+            &admin_signature,
+            &token_state.admin,
+            token_state.strict_sig_position,
+        )?;
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(current_timestamp)?;
+
+        token_state.apply_config_update(&signed_config.config, ctx.accounts.mint.supply)?;
+        token_state.config_nonce = token_state.config_nonce
+            .checked_add(1)
+            .ok_or(RiyalError::NonceOverflow)?;
+
+        msg!(
+            "SIGNED CONFIG APPLIED: Admin: {}, Relayer: {}, Nonce: {}, {}",
+            token_state.admin,
+            ctx.accounts.relayer.key(),
+            signed_config.nonce,
+            token_state.config_summary()
+        );
+
+        Ok(())
+    }
+
+    /// Set the clock drift tolerated in claim expiry and time-lock checks, 0..=120 seconds (admin only)
+    /// A tolerance of N seconds also lets signed claims be redeemed up to N seconds after expiry
+    pub fn set_clock_skew_tolerance(
+        ctx: Context<SetClockSkewTolerance>,
+        clock_skew_tolerance: i64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Keep the tolerance small and non-negative
+        require!(
+            (0..=TokenState::MAX_CLOCK_SKEW_TOLERANCE).contains(&clock_skew_tolerance),
+            RiyalError::InvalidClockSkewTolerance
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_tolerance = token_state.clock_skew_tolerance;
+        token_state.clock_skew_tolerance = clock_skew_tolerance;
+
+        msg!(
+            "CLOCK SKEW TOLERANCE UPDATED: Admin: {}, Tolerance: {} → {} seconds",
+            ctx.accounts.admin.key(),
+            old_tolerance,
+            clock_skew_tolerance
+        );
+
+        Ok(())
+    }
+
+    /// Set the per-user daily claim amount cap in raw token units, 0 removes the cap (admin only)
+    pub fn set_daily_amount_cap(
+        ctx: Context<SetDailyAmountCap>,
+        daily_amount_cap: u64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_cap = token_state.daily_amount_cap;
+        token_state.daily_amount_cap = daily_amount_cap;
+
+        msg!(
+            "DAILY AMOUNT CAP UPDATED: Admin: {}, Cap: {} → {}",
+            ctx.accounts.admin.key(),
+            old_cap,
+            daily_amount_cap
+        );
+
+        Ok(())
+    }
+
+    /// Set the Merkle root of allowlisted claimers and whether claims must prove membership (admin only)
+    pub fn set_allowlist_root(
+        ctx: Context<SetAllowlistRoot>,
+        allowlist_root: [u8; 32],
+        require_allowlist: bool,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_require = token_state.require_allowlist;
+        token_state.allowlist_root = allowlist_root;
+        token_state.require_allowlist = require_allowlist;
+
+        msg!(
+            "ALLOWLIST UPDATED: Admin: {}, Root: {:?}, Required: {} → {}",
+            ctx.accounts.admin.key(),
+            allowlist_root,
+            old_require,
+            require_allowlist
+        );
+
+        Ok(())
+    }
+
+    /// Rotate the hot key that signs claims, Pubkey::default reverts to the admin key (admin only)
+    pub fn set_claim_signer(
+        ctx: Context<SetClaimSigner>,
+        claim_signer: Pubkey,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_signer = token_state.claim_signer_key();
+        token_state.claim_signer = claim_signer;
+
+        msg!(
+            "CLAIM SIGNER UPDATED: Admin: {}, Signer: {} → {}",
+            ctx.accounts.admin.key(),
+            old_signer,
+            token_state.claim_signer_key()
+        );
+
+        Ok(())
+    }
+
+    /// Replace the loyalty reward tiers, sorted by strictly increasing min_claims (admin only)
+    /// Multipliers scale the admin-signed base amount on-chain, so signers and supply
+    /// planning must account for them. An empty list disables tiering.
+    pub fn set_reward_tiers(
+        ctx: Context<SetRewardTiers>,
+        reward_tiers: Vec<RewardTier>,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Validate tier count, ordering and multiplier bounds
+        require!(
+            reward_tiers.len() <= TokenState::MAX_REWARD_TIERS,
+            RiyalError::InvalidRewardTiers
+        );
+        require!(
+            reward_tiers.windows(2).all(|pair| pair[0].min_claims < pair[1].min_claims),
+            RiyalError::InvalidRewardTiers
+        );
+        require!(
+            reward_tiers
+                .iter()
+                .all(|tier| tier.multiplier_bps > 0 && tier.multiplier_bps <= TokenState::MAX_REWARD_MULTIPLIER_BPS),
+            RiyalError::InvalidRewardTiers
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let tier_count = reward_tiers.len();
+        token_state.reward_tiers = reward_tiers;
+
+        msg!(
+            "REWARD TIERS UPDATED: Admin: {}, Tiers: {}",
+            ctx.accounts.admin.key(),
+            tier_count
+        );
+
+        Ok(())
+    }
+
+    /// Toggle suppression of informational claim/transfer logs (admin only)
+    /// Quiet mode drops the signature-success, claim-success, hook and transfer-success logs.
+    /// CLAIM_REJECT diagnostics and emit! events are never suppressed.
+    /// Compute delta: the "Compute: Quiet Logging" tests simulate one claim and one transfer with
+    /// quiet mode off and on and log the CU of each, so the per-claim and per-transfer saving is
+    /// measured against the deployed build rather than estimated from syscall costs.
+    pub fn set_quiet_logging(
+        ctx: Context<SetQuietLogging>,
+        quiet_logging: bool,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        let old_quiet = token_state.quiet_logging;
+        token_state.quiet_logging = quiet_logging;
+
+        msg!(
+            "QUIET LOGGING UPDATED: Admin: {}, Quiet: {} → {}",
+            ctx.accounts.admin.key(),
+            old_quiet,
+            quiet_logging
+        );
+
+        Ok(())
+    }
+
+    /// Set the per-claim cooldown growth in basis points, 0 keeps the cooldown flat (admin only)
+    pub fn set_claim_period_growth(
+        ctx: Context<SetClaimPeriodGrowth>,
+        claim_period_growth_bps: u16,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_growth = token_state.claim_period_growth_bps;
+        token_state.claim_period_growth_bps = claim_period_growth_bps;
+
+        msg!(
+            "CLAIM PERIOD GROWTH UPDATED: Admin: {}, Growth: {} → {} bps per claim",
+            ctx.accounts.admin.key(),
+            old_growth,
+            claim_period_growth_bps
+        );
+
+        Ok(())
+    }
+
+    /// Set the maximum balance a claim may leave in a frozen account, 0 removes the cap (admin only)
+    pub fn set_max_frozen_per_account(
+        ctx: Context<SetMaxFrozenPerAccount>,
+        max_frozen_per_account: u64,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_max_frozen = token_state.max_frozen_per_account;
+        token_state.max_frozen_per_account = max_frozen_per_account;
+
+        msg!(
+            "FROZEN BALANCE CAP UPDATED: Admin: {}, Max frozen per account: {} → {}",
+            ctx.accounts.admin.key(),
+            old_max_frozen,
+            max_frozen_per_account
+        );
+
+        Ok(())
+    }
+
+    /// Set or clear (Pubkey::default) the program invoked after each successful claim (admin only)
+    pub fn set_post_claim_hook(
+        ctx: Context<SetPostClaimHook>,
+        post_claim_hook: Pubkey,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: The hook may not be this program or the token program
+        require!(
+            post_claim_hook != crate::ID && post_claim_hook != anchor_spl::token::ID,
+            RiyalError::InvalidPostClaimHook
+        );
+
+        // Enforce minimum time between admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        let old_hook = token_state.post_claim_hook;
+        token_state.post_claim_hook = post_claim_hook;
+
+        msg!(
+            "POST-CLAIM HOOK UPDATED: Admin: {}, Hook: {} → {}",
+            ctx.accounts.admin.key(),
+            old_hook,
+            post_claim_hook
+        );
+
+        Ok(())
+    }
+
+    /// Read claim status for several users at once - user data PDAs are passed as remaining accounts
+    /// Returns statuses in order; fewer than requested means compute ran low and the rest should be resubmitted
+    pub fn get_batch_claim_status(ctx: Context<GetBatchClaimStatus>) -> Result<Vec<UserClaimStatus>> {
+        // Verify the batch fits in the return data buffer
+        require!(
+            ctx.remaining_accounts.len() <= MAX_BATCH_STATUS_ACCOUNTS,
+            RiyalError::TooManyAccounts
+        );
+
+        let mut statuses = Vec::with_capacity(ctx.remaining_accounts.len());
+        for account in ctx.remaining_accounts.iter() {
+            // Stop cleanly before exhausting compute - the returned length tells the client
+            // how many accounts were processed so it can resubmit the remainder
+            if sol_remaining_compute_units() < MIN_COMPUTE_PER_BATCH_ITEM {
+                msg!(
+                    "BATCH STOPPED EARLY: Processed {} of {} accounts",
+                    statuses.len(),
+                    ctx.remaining_accounts.len()
+                );
+                break;
+            }
+
+            // Only accept UserData accounts owned by this program
+            require!(
+                account.owner == ctx.program_id,
+                RiyalError::InvalidUserData
+            );
+            let data = account.try_borrow_data()?;
+            let user_data = UserData::try_deserialize(&mut &data[..])
+                .map_err(|_| RiyalError::InvalidUserData)?;
+
+            statuses.push(UserClaimStatus {
+                nonce: user_data.nonce,
+                total_claims: user_data.total_claims,
+                next_allowed_claim_time: user_data.next_allowed_claim_time,
+            });
+        }
+
+        Ok(statuses)
+    }
+
+    /// The cooldown that actually applies to the user's next claim, with every override resolved
+    /// Precedence: signed bypass > slot mode > escalated > global. A bypass is carried per-claim in the
+    /// signed payload, so it cannot be known here - a bypassed claim ignores the times returned.
+    /// There are no per-user custom periods.
+    pub fn get_effective_cooldown(ctx: Context<GetEffectiveCooldown>, _user: Pubkey) -> Result<EffectiveCooldown> {
+        let token_state = &ctx.accounts.token_state;
+        let user_data = &ctx.accounts.user_data;
+
+        let next_total_claims = user_data.total_claims.saturating_add(1);
+        let (source, cooldown) = if !token_state.time_lock_enabled {
+            (cooldown_source::MINIMUM_GAP, 1)
+        } else if token_state.use_slot_timelock {
+            (cooldown_source::SLOT, token_state.claim_period_slots.min(i64::MAX as u64) as i64)
+        } else if token_state.claim_period_growth_bps > 0 {
+            (cooldown_source::ESCALATED, token_state.effective_claim_period(next_total_claims))
+        } else {
+            (cooldown_source::GLOBAL, token_state.effective_claim_period(next_total_claims))
+        };
+
+        Ok(EffectiveCooldown {
+            source,
+            cooldown,
+            next_allowed_claim_time: user_data.next_allowed_claim_time,
+            next_allowed_claim_slot: user_data.next_allowed_claim_slot,
+        })
+    }
+
+    /// Next `count` timestamps at which the user may claim, assuming each claim is made as soon as allowed
+    /// Applies the escalating cooldown (claim_period_growth_bps) per future claim; unavailable in slot mode
+    pub fn get_claim_schedule(ctx: Context<GetClaimSchedule>, count: u8) -> Result<Vec<i64>> {
+        let token_state = &ctx.accounts.token_state;
+        let user_data = &ctx.accounts.user_data;
+
+        // Verify the schedule fits in the return data buffer
+        require!(
+            count > 0 && count <= MAX_CLAIM_SCHEDULE_ENTRIES,
+            RiyalError::InvalidScheduleCount
+        );
+
+        // Slot-based cooldowns have no authoritative wall-clock equivalent
+        require!(
+            !(token_state.time_lock_enabled && token_state.use_slot_timelock),
+            RiyalError::ClaimScheduleUnavailable
+        );
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let mut next_claim_time = user_data.next_allowed_claim_time.max(current_timestamp);
+        let mut schedule = Vec::with_capacity(count as usize);
+        for claim_index in 0..count as u64 {
+            schedule.push(next_claim_time);
+
+            // The period after a claim depends on the claim count it brings the user to
+            let period = if token_state.time_lock_enabled {
+                token_state.effective_claim_period(user_data.total_claims.saturating_add(claim_index + 1))
+            } else {
+                1 // Minimum gap between claims without a time-lock
+            };
+            next_claim_time = next_claim_time.saturating_add(period);
+        }
+
+        Ok(schedule)
+    }
+
+    /// Remaining mintable supply under the cap, read from the live mint (u64::MAX when uncapped)
+    pub fn get_remaining_mintable(ctx: Context<GetRemainingMintable>) -> Result<u64> {
+        let token_state = &ctx.accounts.token_state;
+
+        if token_state.max_supply == 0 {
+            return Ok(u64::MAX);
+        }
+
+        Ok(token_state.max_supply.saturating_sub(ctx.accounts.mint.supply))
+    }
+
+    /// Versioned layout of every admin-signed message, so SDKs can derive signing code
+    pub fn get_message_schema(_ctx: Context<GetMessageSchema>) -> Result<MessageSchema> {
+        Ok(message_schema())
+    }
+
+    /// Schema version, active feature bits and key config values, so one client can adapt to any deployment
+    pub fn get_capabilities(ctx: Context<GetCapabilities>) -> Result<Capabilities> {
+        let token_state = &ctx.accounts.token_state;
+
+        let mut features = 0u64;
+        for (enabled, bit) in [
+            (token_state.time_lock_enabled, capability::TIME_LOCK),
+            (token_state.time_lock_enabled && token_state.use_slot_timelock, capability::SLOT_TIME_LOCK),
+            (token_state.claim_period_growth_bps > 0, capability::ESCALATING_COOLDOWN),
+            (token_state.use_oracle_pricing, capability::ORACLE_PRICING),
+            (token_state.require_allowlist, capability::ALLOWLIST),
+            (token_state.max_supply > 0, capability::SUPPLY_CAP),
+            (token_state.max_claims_per_user > 0, capability::CLAIM_QUOTA),
+            (token_state.daily_amount_cap > 0, capability::DAILY_AMOUNT_CAP),
+            (!token_state.reward_tiers.is_empty(), capability::REWARD_TIERS),
+            (token_state.post_claim_hook != Pubkey::default(), capability::POST_CLAIM_HOOK),
+            (token_state.claim_signer != Pubkey::default(), capability::SEPARATE_CLAIM_SIGNER),
+            (token_state.transfers_enabled, capability::TRANSFERS_ENABLED),
+            (token_state.transfers_permanently_enabled, capability::TRANSFERS_PERMANENT),
+            (token_state.signature_claims_paused, capability::SIGNATURE_CLAIMS_PAUSED),
+            (token_state.strict_sig_position, capability::STRICT_SIG_POSITION),
+            (token_state.single_claim_mode, capability::SINGLE_CLAIM_MODE),
+            (token_state.require_transfer_approval, capability::TRANSFER_APPROVAL),
+            (token_state.anomaly_multiplier > 0, capability::ANOMALY_GUARD),
+            (token_state.claim_burn_bps > 0, capability::CLAIM_BURN),
+        ] {
+            if enabled {
+                features |= bit;
+            }
+        }
+
+        Ok(Capabilities {
+            state_version: TokenState::STATE_VERSION,
+            features,
+            disabled_instructions: token_state.disabled_instructions,
+            claim_period_seconds: token_state.claim_period_seconds,
+            claim_period_slots: token_state.claim_period_slots,
+            max_supply: token_state.max_supply,
+            reserved_for_treasury: token_state.reserved_for_treasury,
+            max_claims_per_user: token_state.max_claims_per_user,
+            daily_amount_cap: token_state.daily_amount_cap,
+            decimals: token_state.decimals,
+        })
+    }
+
+    /// Check the supplied mint against token_state.token_mint and the PDA authorities
+    /// Reports every mismatch instead of reverting, so operators can diagnose a bad binding
+    pub fn verify_mint_binding(ctx: Context<VerifyMintBinding>) -> Result<MintBindingReport> {
+        let token_state = &ctx.accounts.token_state;
+        let mint = &ctx.accounts.mint;
+        let pda = token_state.key();
+
+        let report = MintBindingReport {
+            token_mint: token_state.token_mint,
+            supplied_mint: mint.key(),
+            mint_matches: mint.key() == token_state.token_mint,
+            mint_authority_is_pda: mint.mint_authority == COption::Some(pda),
+            freeze_authority_is_pda: mint.freeze_authority == COption::Some(pda),
+            decimals_match: mint.decimals == token_state.decimals,
+            supply: mint.supply,
+        };
+
+        msg!(
+            "MINT BINDING: Stored: {}, Supplied: {}, Matches: {}, Mint authority PDA: {}, Freeze authority PDA: {}, Decimals match: {}",
+            report.token_mint,
+            report.supplied_mint,
+            report.mint_matches,
+            report.mint_authority_is_pda,
+            report.freeze_authority_is_pda,
+            report.decimals_match
+        );
+
+        Ok(report)
+    }
+
+    /// Supply figures for dashboards: live supply, cap and program-maintained counters
+    pub fn get_supply_stats(ctx: Context<GetSupplyStats>) -> Result<SupplyStats> {
+        let token_state = &ctx.accounts.token_state;
+
+        Ok(SupplyStats {
+            current_supply: ctx.accounts.mint.supply,
+            max_supply: token_state.max_supply,
+            lifetime_minted: token_state.lifetime_minted,
+            lifetime_burned: token_state.lifetime_burned,
+            treasury_minted_total: token_state.treasury_minted_total,
+        })
+    }
+
+    /// Verify the PDA holds the mint and freeze authority, moving any held by the admin to the PDA (admin only)
+    /// Idempotent - returns a bitmask of PDA_AUTHORITY_FIXED_* for the authorities that were moved
+    pub fn ensure_pda_authorities(ctx: Context<EnsurePdaAuthorities>) -> Result<u8> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        let pda = token_state.key();
+        let admin = ctx.accounts.admin.key();
+        let mut fixed = 0u8;
+
+        for (current_authority, authority_type, fixed_bit) in [
+            (ctx.accounts.mint.mint_authority, AuthorityType::MintTokens, PDA_AUTHORITY_FIXED_MINT),
+            (ctx.accounts.mint.freeze_authority, AuthorityType::FreezeAccount, PDA_AUTHORITY_FIXED_FREEZE),
+        ] {
+            match current_authority {
+                COption::Some(authority) if authority == pda => {}
+                COption::Some(authority) if authority == admin => {
+                    // Admin still holds this authority - hand it to the PDA
+                    let cpi_accounts = SetAuthority {
+                        current_authority: ctx.accounts.admin.to_account_info(),
+                        account_or_mint: ctx.accounts.mint.to_account_info(),
+                    };
+                    let cpi_program = ctx.accounts.token_program.to_account_info();
+                    set_authority(CpiContext::new(cpi_program, cpi_accounts), authority_type, Some(pda))?;
+                    fixed |= fixed_bit;
+                }
+                // Revoked or held by an unknown key - cannot be repaired from here
+                _ => return err!(RiyalError::PdaAuthorityMismatch),
+            }
+        }
+
+        msg!(
+            "PDA AUTHORITIES ENSURED: Admin: {}, Mint: {}, Mint authority fixed: {}, Freeze authority fixed: {}",
+            admin,
+            ctx.accounts.mint.key(),
+            fixed & PDA_AUTHORITY_FIXED_MINT != 0,
+            fixed & PDA_AUTHORITY_FIXED_FREEZE != 0
+        );
+
+        Ok(fixed)
+    }
+
+    /// Set upgrade authority (current upgrade authority only)
+    pub fn set_upgrade_authority(
+        ctx: Context<SetUpgradeAuthority>,
+        new_upgrade_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify current upgrade authority is calling this function
+        require!(
+            ctx.accounts.current_upgrade_authority.key() == token_state.upgrade_authority,
+            RiyalError::UnauthorizedUpgradeAuthority
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify contract is upgradeable
+        require!(
+            token_state.upgradeable,
+            RiyalError::ContractNotUpgradeable
+        );
+
+        // Get current timestamp for logging
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        let old_authority = token_state.upgrade_authority;
+
+        match new_upgrade_authority {
+            Some(new_auth) => {
+                token_state.upgrade_authority = new_auth;
+                msg!(
+                    "UPGRADE AUTHORITY CHANGED: {} → {}, Timestamp: {}",
+                    old_authority,
+                    new_auth,
+                    current_timestamp
+                );
+            }
+            None => {
+                // Setting to None makes contract non-upgradeable permanently
+                token_state.upgrade_authority = Pubkey::default();
+                token_state.upgradeable = false;
+                msg!(
+                    "UPGRADE AUTHORITY REMOVED: Contract is now IMMUTABLE, Timestamp: {}",
+                    current_timestamp
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate upgrade authorization (called before upgrades)
+    pub fn validate_upgrade(ctx: Context<ValidateUpgrade>) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify upgrade authority is calling this function
+        require!(
+            ctx.accounts.upgrade_authority.key() == token_state.upgrade_authority,
+            RiyalError::UnauthorizedUpgradeAuthority
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify contract is upgradeable
+        require!(
+            token_state.upgradeable,
+            RiyalError::ContractNotUpgradeable
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify program data account
+        require!(
+            ctx.accounts.program_data.key() != Pubkey::default(),
+            RiyalError::InvalidProgramData
+        );
+
+        // Get current timestamp for logging
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        msg!(
+            "UPGRADE VALIDATED: Authority: {}, Program: {}, Timestamp: {} - UPGRADE AUTHORIZED",
+            ctx.accounts.upgrade_authority.key(),
+            ctx.program_id,
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Create contract treasury account (admin only)
+    pub fn create_treasury(ctx: Context<CreateTreasury>) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::TREASURY_SETUP);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify treasury not already created
+        require!(
+            token_state.treasury_account == Pubkey::default(),
+            RiyalError::TreasuryAlreadyCreated
+        );
+
+        // Store treasury account
+        token_state.treasury_account = ctx.accounts.treasury_account.key();
+
+        // Get current timestamp for logging
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        msg!(
+            "TREASURY CREATED: Admin: {}, Treasury Account: {}, Timestamp: {}",
+            ctx.accounts.admin.key(),
+            ctx.accounts.treasury_account.key(),
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Register an existing PDA-owned token account as the treasury instead of creating one (admin only)
+    pub fn register_treasury(ctx: Context<RegisterTreasury>, treasury: Pubkey) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::TREASURY_SETUP);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify treasury not already set
+        require!(
+            token_state.treasury_account == Pubkey::default(),
+            RiyalError::TreasuryAlreadyCreated
+        );
+
+        // CRITICAL SECURITY CHECK 5: Verify the supplied account is the registered treasury
+        require!(
+            ctx.accounts.treasury_account.key() == treasury,
+            RiyalError::InvalidTreasuryAccount
+        );
+
+        // CRITICAL SECURITY CHECK 6: Verify treasury is for the correct mint
+        require!(
+            ctx.accounts.treasury_account.mint == token_state.token_mint,
+            RiyalError::TreasuryMintMismatch
+        );
+
+        // CRITICAL SECURITY CHECK 7: Treasury must be program-controlled, never a personal account
+        require!(
+            ctx.accounts.treasury_account.owner == token_state.key(),
+            RiyalError::InvalidTreasuryAuthority
+        );
+
+        // A delegate or close authority could move or close program funds behind the PDA's back
+        require!(
+            ctx.accounts.treasury_account.delegate.is_none() && ctx.accounts.treasury_account.close_authority.is_none(),
+            RiyalError::InvalidTreasuryAuthority
+        );
+
+        // Store treasury account
+        token_state.treasury_account = treasury;
+
+        // Get current timestamp for logging
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        msg!(
+            "TREASURY REGISTERED: Admin: {}, Treasury Account: {}, Balance: {}, Timestamp: {}",
+            ctx.accounts.admin.key(),
+            treasury,
+            ctx.accounts.treasury_account.amount,
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Mint tokens to contract treasury (admin only)
+    pub fn mint_to_treasury(
+        ctx: Context<MintToTreasury>,
+        amount: u64,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::TREASURY_MINT);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify treasury has been created
+        require!(
+            token_state.treasury_account != Pubkey::default(),
+            RiyalError::TreasuryNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 5: Verify treasury account matches stored account
+        require!(
+            ctx.accounts.treasury_account.key() == token_state.treasury_account,
+            RiyalError::InvalidTreasuryAccount
+        );
+
+        // Fail clearly on a wrong-mint treasury rather than inside the SPL CPI
+        require!(
+            ctx.accounts.treasury_account.mint == token_state.token_mint,
+            RiyalError::TreasuryMintMismatch
+        );
+
+        // Treasury mints must land in a program-controlled account, never a personal one
+        require!(
+            ctx.accounts.treasury_account.owner == token_state.key(),
+            RiyalError::InvalidTreasuryAuthority
+        );
+
+        // Verify the treasury account itself has not been denylisted
+        require!(
+            !token_state.is_mint_denied(&ctx.accounts.treasury_account.key()),
+            RiyalError::DestinationDenied
+        );
+
+        // CRITICAL SECURITY CHECK 6: Verify amount is not zero
+        require!(
+            amount > 0,
+            RiyalError::InvalidMintAmount
+        );
+
+        // CRITICAL SECURITY CHECK 7: Verify the mint stays within the supply cap
+        if token_state.max_supply > 0 {
+            require!(
+                ctx.accounts.mint.supply
+                    .checked_add(amount)
+                    .is_some_and(|supply_after| supply_after <= token_state.max_supply),
+                RiyalError::MaxSupplyExceeded
+            );
+        }
+
+        // Create PDA signer for minting
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[ctx.accounts.token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Create CPI context for minting to treasury
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.treasury_account.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        // Mint tokens to treasury
+        mint_to(cpi_ctx, amount)?;
+
+        // Get current timestamp for logging
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        // Track lifetime issuance in the same instruction as the mint
+        let token_state = &mut ctx.accounts.token_state;
+        token_state.lifetime_minted = token_state.lifetime_minted
+            .checked_add(amount)
+            .ok_or(RiyalError::SupplyCounterOverflow)?;
+        token_state.treasury_minted_total = token_state.treasury_minted_total
+            .checked_add(amount)
+            .ok_or(RiyalError::SupplyCounterOverflow)?;
+
+        msg!(
+            "MINTED TO TREASURY: Admin: {}, Amount: {}, Treasury: {}, Timestamp: {}",
+            ctx.accounts.admin.key(),
+            amount,
+            ctx.accounts.treasury_account.key(),
+            current_timestamp
+        );
+
+        // Read the post-mint treasury balance for the event
+        ctx.accounts.treasury_account.reload()?;
+        emit!(TreasuryEvent {
+            op: TreasuryEvent::OP_MINT,
+            treasury: ctx.accounts.treasury_account.key(),
+            amount,
+            balance_after: ctx.accounts.treasury_account.amount,
+            decimals: ctx.accounts.token_state.decimals,
+            timestamp: current_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Burn tokens from contract treasury (admin only)
+    pub fn burn_from_treasury(
+        ctx: Context<BurnFromTreasury>,
+        amount: u64,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::BURN);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify treasury has been created
+        require!(
+            token_state.treasury_account != Pubkey::default(),
+            RiyalError::TreasuryNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 5: Verify treasury account matches stored account
+        require!(
+            ctx.accounts.treasury_account.key() == token_state.treasury_account,
+            RiyalError::InvalidTreasuryAccount
+        );
+
+        // Fail clearly on a wrong-mint treasury rather than inside the SPL CPI
+        require!(
+            ctx.accounts.treasury_account.mint == token_state.token_mint,
+            RiyalError::TreasuryMintMismatch
+        );
+
+        // CRITICAL SECURITY CHECK 6: Verify amount is not zero
+        require!(
+            amount > 0,
+            RiyalError::InvalidBurnAmount
+        );
+
+        // CRITICAL SECURITY CHECK 7: Verify treasury has sufficient balance
+        require!(
+            ctx.accounts.treasury_account.amount >= amount,
+            RiyalError::InsufficientTreasuryBalance
+        );
+
+        // Create PDA signer for burning from treasury
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[ctx.accounts.token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Create CPI context for burning from treasury
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.treasury_account.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        // Burn tokens from treasury
+        burn(cpi_ctx, amount)?;
+
+        // Get current timestamp for logging
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        // Track lifetime burns in the same instruction as the burn
+        let token_state = &mut ctx.accounts.token_state;
+        token_state.lifetime_burned = token_state.lifetime_burned
+            .checked_add(amount)
+            .ok_or(RiyalError::SupplyCounterOverflow)?;
+        // The treasury may also hold tokens it did not mint, so the net figure floors at zero
+        token_state.treasury_minted_total = token_state.treasury_minted_total.saturating_sub(amount);
+
+        // Read the post-burn treasury balance
+        ctx.accounts.treasury_account.reload()?;
+        let balance_after = ctx.accounts.treasury_account.amount;
+
+        msg!(
+            "BURNED FROM TREASURY: Admin: {}, Amount: {}, Treasury: {}, Timestamp: {}, Remaining: {}",
+            ctx.accounts.admin.key(),
+            amount,
+            ctx.accounts.treasury_account.key(),
+            current_timestamp,
+            balance_after
+        );
+
+        emit!(TreasuryEvent {
+            op: TreasuryEvent::OP_BURN,
+            treasury: ctx.accounts.treasury_account.key(),
+            amount,
+            balance_after,
+            decimals: ctx.accounts.token_state.decimals,
+            timestamp: current_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Migrate the contract treasury to a new PDA-owned token account (admin only)
+    pub fn set_treasury(
+        ctx: Context<SetTreasury>,
+        new_treasury: Pubkey,
+        drain_old_treasury: bool,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::TREASURY_SETUP);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify token mint has been created
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify a treasury exists to migrate from
+        require!(
+            token_state.treasury_account != Pubkey::default(),
+            RiyalError::TreasuryNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 5: Verify old treasury account matches stored account
+        require!(
+            ctx.accounts.old_treasury_account.key() == token_state.treasury_account,
+            RiyalError::InvalidTreasuryAccount
+        );
+
+        // CRITICAL SECURITY CHECK 6: Verify the new treasury is a different account (a drain into itself is a no-op)
+        require!(
+            new_treasury != token_state.treasury_account,
+            RiyalError::TreasuryTransferToSelf
+        );
+
+        // CRITICAL SECURITY CHECK 7: Verify new treasury is for the correct mint and owned by the PDA
+        require!(
+            ctx.accounts.new_treasury_account.key() == new_treasury,
+            RiyalError::InvalidTreasuryAccount
+        );
+
+        require!(
+            ctx.accounts.new_treasury_account.mint == token_state.token_mint,
+            RiyalError::TreasuryMintMismatch
+        );
+
+        // Treasury must be program-controlled, never a personal account
+        require!(
+            ctx.accounts.new_treasury_account.owner == token_state.key(),
+            RiyalError::InvalidTreasuryAuthority
+        );
+
+        // A delegate or close authority could move or close program funds behind the PDA's back
+        require!(
+            ctx.accounts.new_treasury_account.delegate.is_none() && ctx.accounts.new_treasury_account.close_authority.is_none(),
+            RiyalError::InvalidTreasuryAuthority
+        );
+
+        let old_treasury = token_state.treasury_account;
+        let drained_amount = if drain_old_treasury {
+            ctx.accounts.old_treasury_account.amount
+        } else {
+            0
+        };
+
+        // Move the old treasury balance across before switching over
+        if drained_amount > 0 {
+            let seeds = &[
+                b"token_state".as_ref(),
+                &[ctx.accounts.token_state.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.old_treasury_account.to_account_info(),
+                to: ctx.accounts.new_treasury_account.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+            transfer(cpi_ctx, drained_amount)?;
+        }
+
+        let token_state = &mut ctx.accounts.token_state;
+
+        // CRITICAL SECURITY CHECK 8: Rate-limit admin config changes
+        token_state.record_config_change(Clock::get()?.unix_timestamp)?;
+
+        token_state.treasury_account = new_treasury;
+
+        // Get current timestamp for logging
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        msg!(
+            "TREASURY UPDATED: Admin: {}, Treasury: {} → {}, Drained: {}, Timestamp: {}",
+            ctx.accounts.admin.key(),
+            old_treasury,
+            new_treasury,
+            drained_amount,
+            current_timestamp
+        );
+
+        // Record the drain as a transfer out of the old treasury
+        if drained_amount > 0 {
+            ctx.accounts.old_treasury_account.reload()?;
+            emit!(TreasuryEvent {
+                op: TreasuryEvent::OP_TRANSFER,
+                treasury: old_treasury,
+                amount: drained_amount,
+                balance_after: ctx.accounts.old_treasury_account.amount,
+                decimals: ctx.accounts.token_state.decimals,
+                timestamp: current_timestamp,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Register an additional SPL mint managed under the same TokenState (admin only)
+    pub fn add_managed_mint(
+        ctx: Context<AddManagedMint>,
+        decimals: u8,
+    ) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::ADD_MINT);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Primary mint must exist (it is always mint index 0)
+        require!(
+            token_state.token_mint != Pubkey::default(),
+            RiyalError::TokenMintNotCreated
+        );
+
+        // CRITICAL SECURITY CHECK 4: Keep the managed mint list bounded
+        require!(
+            token_state.additional_mints.len() < TokenState::MAX_ADDITIONAL_MINTS,
+            RiyalError::TooManyManagedMints
+        );
+
+        token_state.additional_mints.push(ctx.accounts.mint.key());
+        let mint_index = token_state.additional_mints.len();
+
+        msg!(
+            "MANAGED MINT ADDED: Admin: {}, Mint: {}, Index: {}, Decimals: {}",
+            ctx.accounts.admin.key(),
+            ctx.accounts.mint.key(),
+            mint_index,
+            decimals
+        );
+
+        Ok(())
+    }
+
+    /// Mint tokens of a managed mint to a user's token account (admin only)
+    pub fn mint_managed_tokens(
+        ctx: Context<MintManagedTokens>,
+        mint_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::MINT);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // The primary mint (index 0) must go through mint_tokens, which enforces the supply cap and updates lifetime_minted
+        require!(
+            mint_index != 0,
+            RiyalError::PrimaryMintIndex
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify the mint account matches the selected managed mint
+        require!(
+            token_state.managed_mint(mint_index) == Some(ctx.accounts.mint.key()),
+            RiyalError::InvalidTokenMint
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify the token account is for the selected mint
+        require!(
+            ctx.accounts.user_token_account.mint == ctx.accounts.mint.key(),
+            RiyalError::InvalidTokenAccount
+        );
+
+        // CRITICAL SECURITY CHECK 5: Verify amount is not zero
+        require!(
+            amount > 0,
+            RiyalError::InvalidMintAmount
+        );
+
+        // CRITICAL SECURITY CHECK 6: The admin may not mint to its own accounts once forbidden
+        require!(
+            !token_state.self_mint_forbidden || ctx.accounts.user_token_account.owner != ctx.accounts.admin.key(),
+            RiyalError::SelfMintForbidden
+        );
+
+        // CRITICAL SECURITY CHECK 7: Verify the destination is not denylisted
+        require!(
+            !token_state.is_mint_denied(&ctx.accounts.user_token_account.owner)
+                && !token_state.is_mint_denied(&ctx.accounts.user_token_account.key()),
+            RiyalError::DestinationDenied
+        );
+
+        // Create PDA signer for minting
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[ctx.accounts.token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Accounts left frozen by an earlier mint must be thawed first - they are re-frozen below
+        if ctx.accounts.user_token_account.is_frozen() {
+            let thaw_cpi_accounts = ThawAccount {
+                account: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+            let thaw_cpi_ctx = CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds);
+
+            thaw_account(thaw_cpi_ctx)?;
+        }
+
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        mint_to(cpi_ctx, amount)?;
+
+        // AUTO-FREEZE: Immediately freeze the token account after minting
+        let freeze_cpi_accounts = FreezeAccount {
+            account: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let freeze_cpi_program = ctx.accounts.token_program.to_account_info();
+        let freeze_cpi_ctx = CpiContext::new_with_signer(freeze_cpi_program, freeze_cpi_accounts, signer_seeds);
+
+        freeze_account(freeze_cpi_ctx)?;
+
+        msg!(
+            "Minted {} tokens of managed mint {} (index {}) to user account: {} by admin: {} - ACCOUNT IMMEDIATELY FROZEN",
+            amount,
+            ctx.accounts.mint.key(),
+            mint_index,
+            ctx.accounts.user_token_account.key(),
+            ctx.accounts.admin.key()
+        );
+
+        Ok(())
+    }
+
+    /// Burn tokens of a managed mint from a user's account (admin authorized, user must sign)
+    pub fn burn_managed_tokens(
+        ctx: Context<BurnManagedTokens>,
+        mint_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::BURN);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // The primary mint (index 0) must go through burn_tokens, which updates lifetime_burned
+        require!(
+            mint_index != 0,
+            RiyalError::PrimaryMintIndex
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify the mint account matches the selected managed mint
+        require!(
+            token_state.managed_mint(mint_index) == Some(ctx.accounts.mint.key()),
+            RiyalError::InvalidTokenMint
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify the token account is for the selected mint
+        require!(
+            ctx.accounts.user_token_account.mint == ctx.accounts.mint.key(),
+            RiyalError::InvalidTokenAccount
+        );
+
+        // CRITICAL SECURITY CHECK 5: Verify amount is not zero
+        require!(
+            amount > 0,
+            RiyalError::InvalidBurnAmount
+        );
+
+        // CRITICAL SECURITY CHECK 6: Verify user has sufficient balance to burn
+        require!(
+            ctx.accounts.user_token_account.amount >= amount,
+            RiyalError::InsufficientBalance
+        );
+
+        // CRITICAL SECURITY CHECK 7: Verify user is the owner of the token account
+        require!(
+            ctx.accounts.user_token_account.owner == ctx.accounts.user_authority.key(),
+            RiyalError::UnauthorizedBurn
+        );
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.user_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        burn(cpi_ctx, amount)?;
+
+        msg!(
+            "MANAGED BURN SUCCESSFUL: Admin: {}, User: {}, Mint: {} (index {}), Amount Burned: {}",
+            ctx.accounts.admin.key(),
+            ctx.accounts.user_authority.key(),
+            ctx.accounts.mint.key(),
+            mint_index,
+            amount
+        );
+
+        Ok(())
+    }
+
+    /// Freeze a user's token account of a managed mint (admin only)
+    pub fn freeze_managed_account(
+        ctx: Context<FreezeManagedAccount>,
+        mint_index: u8,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // Reject retired instruction families
+        require_enabled!(token_state, instruction_family::FREEZE);
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify the mint account matches the selected managed mint
+        require!(
+            token_state.managed_mint(mint_index) == Some(ctx.accounts.mint.key()),
+            RiyalError::InvalidTokenMint
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify the token account is for the selected mint
+        require!(
+            ctx.accounts.token_account.mint == ctx.accounts.mint.key(),
+            RiyalError::InvalidTokenAccount
+        );
+
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[ctx.accounts.token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = FreezeAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        freeze_account(cpi_ctx)?;
+
+        msg!(
+            "Token account {} of managed mint {} (index {}) FROZEN by admin: {}",
+            ctx.accounts.token_account.key(),
+            ctx.accounts.mint.key(),
+            mint_index,
+            ctx.accounts.admin.key()
+        );
+
+        Ok(())
+    }
+
+    /// Unfreeze a user's token account of a managed mint (admin only)
+    pub fn unfreeze_managed_account(
+        ctx: Context<UnfreezeManagedAccount>,
+        mint_index: u8,
+    ) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+        
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 3: Verify the mint account matches the selected managed mint
+        require!(
+            token_state.managed_mint(mint_index) == Some(ctx.accounts.mint.key()),
+            RiyalError::InvalidTokenMint
+        );
+
+        // CRITICAL SECURITY CHECK 4: Verify the token account is for the selected mint
+        require!(
+            ctx.accounts.token_account.mint == ctx.accounts.mint.key(),
+            RiyalError::InvalidTokenAccount
+        );
+
+        let seeds = &[
+            b"token_state".as_ref(),
+            &[ctx.accounts.token_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = ThawAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        thaw_account(cpi_ctx)?;
+
+        msg!(
+            "Token account {} of managed mint {} (index {}) UNFROZEN by admin: {}",
+            ctx.accounts.token_account.key(),
+            ctx.accounts.mint.key(),
+            mint_index,
+            ctx.accounts.admin.key()
+        );
+
+        Ok(())
+    }
+
+    /// Start replacing a lost admin key - requires recovery_threshold distinct recovery signers
+    /// Needs Ed25519 verify instructions over "RIYAL_RECOVERY_V1" | program_id | new_admin | recovery_nonce (LE)
+    /// The new admin only takes over after RECOVERY_TIMELOCK_SECONDS, during which the current
+    /// admin can cancel; any relayer may submit
+    pub fn recover_admin(ctx: Context<RecoverAdmin>, new_admin: Pubkey) -> Result<()> {
+        let token_state = &ctx.accounts.token_state;
+
+        // CRITICAL SECURITY CHECK 1: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        // CRITICAL SECURITY CHECK 2: Recovery must have been configured at initialize
+        require!(
+            token_state.recovery_threshold > 0,
+            RiyalError::RecoveryNotConfigured
+        );
+
+        // CRITICAL SECURITY CHECK 3: One recovery at a time
+        require!(
+            token_state.pending_recovery_admin == Pubkey::default(),
+            RiyalError::RecoveryAlreadyPending
+        );
+
+        // CRITICAL SECURITY CHECK 4: The new admin must be a real, different key
+        require!(
+            new_admin != Pubkey::default() && new_admin != token_state.admin,
+            RiyalError::InvalidRecoveryAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 5: Verify the threshold of recovery signers approved this exact request
+        let mut message_bytes = Vec::new();
+        message_bytes.extend_from_slice(RECOVERY_MESSAGE_PREFIX);
+        message_bytes.extend_from_slice(&crate::ID.to_bytes());
+        message_bytes.extend_from_slice(&new_admin.to_bytes());
+        message_bytes.extend_from_slice(&token_state.recovery_nonce.to_le_bytes());
+        let signatures = count_threshold_signers(
+            &ctx.accounts.instructions,
+            &message_bytes,
+            &token_state.recovery_signers,
+        )?;
+        require!(
+            signatures >= token_state.recovery_threshold as usize,
+            RiyalError::InsufficientRecoverySignatures
+        );
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let executable_at = current_timestamp
+            .checked_add(TokenState::RECOVERY_TIMELOCK_SECONDS)
+            .ok_or(RiyalError::TimestampOverflow)?;
+
+        // Consume the nonce so the approvals cannot be replayed after a cancel
+        let token_state = &mut ctx.accounts.token_state;
+        token_state.recovery_nonce = token_state.recovery_nonce.saturating_add(1);
+        token_state.pending_recovery_admin = new_admin;
+        token_state.recovery_executable_at = executable_at;
+
+        emit!(AdminRecoveryEvent {
+            op: AdminRecoveryEvent::OP_INITIATED,
+            admin: token_state.admin,
+            new_admin,
+            executable_at,
+            timestamp: current_timestamp,
+        });
+
+        msg!(
+            "ADMIN RECOVERY INITIATED: Admin: {} → {}, Signatures: {}/{}, Executable at: {} - CURRENT ADMIN CAN CANCEL UNTIL THEN",
+            token_state.admin,
+            new_admin,
+            signatures,
+            token_state.recovery_threshold,
+            executable_at
+        );
+
+        Ok(())
+    }
+
+    /// Hand the admin role to the pending recovery admin once the timelock has passed (anyone may call)
+    pub fn finalize_admin_recovery(ctx: Context<FinalizeAdminRecovery>) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+
+        // CRITICAL SECURITY CHECK 1: A recovery must be pending
+        require!(
+            token_state.pending_recovery_admin != Pubkey::default(),
+            RiyalError::NoPendingRecovery
+        );
+
+        // CRITICAL SECURITY CHECK 2: The mandatory timelock must have fully elapsed
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            current_timestamp >= token_state.recovery_executable_at,
+            RiyalError::RecoveryTimelockActive
+        );
+
+        let old_admin = token_state.admin;
+        let new_admin = token_state.pending_recovery_admin;
+
+        // CRITICAL SECURITY CHECK 3: Once enabled, the admin history cannot be skipped
+        if token_state.admin_history_enabled {
+            let admin_history = ctx
+                .accounts
+                .admin_history
+                .as_mut()
+                .ok_or(RiyalError::AdminHistoryRequired)?;
+            admin_history.record(new_admin, current_timestamp);
+        }
+
+        token_state.admin = new_admin;
+        token_state.pending_recovery_admin = Pubkey::default();
+        token_state.recovery_executable_at = 0;
+
+        emit!(AdminRecoveryEvent {
+            op: AdminRecoveryEvent::OP_COMPLETED,
+            admin: old_admin,
+            new_admin,
+            executable_at: current_timestamp,
+            timestamp: current_timestamp,
+        });
+
+        msg!(
+            "ADMIN RECOVERED: Admin: {} → {}, Timestamp: {} - ADMIN KEY REPLACED",
+            old_admin,
+            new_admin,
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Create the admin history ring buffer, seeded with the current admin (admin only, once)
+    pub fn initialize_admin_history(ctx: Context<InitializeAdminHistory>) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify contract is initialized
+        require!(
+            token_state.is_initialized,
+            RiyalError::ContractNotInitialized
+        );
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let admin_history = &mut ctx.accounts.admin_history;
+        admin_history.entries = Vec::new();
+        admin_history.total_changes = 0;
+        admin_history.bump = ctx.bumps.admin_history;
+        admin_history.record(token_state.admin, current_timestamp);
+
+        token_state.admin_history_enabled = true;
+
+        msg!(
+            "Admin history initialized by admin: {}, Capacity: {}",
+            token_state.admin,
+            AdminHistory::MAX_ENTRIES
+        );
+
+        Ok(())
+    }
+
+    /// View the recorded chain of admin custody, oldest retained entry first
+    pub fn get_admin_history(ctx: Context<GetAdminHistory>) -> Result<AdminHistory> {
+        Ok(ctx.accounts.admin_history.clone().into_inner())
+    }
+
+    /// Cancel a pending admin recovery (current admin only) - proves the admin key is not lost
+    pub fn cancel_admin_recovery(ctx: Context<CancelAdminRecovery>) -> Result<()> {
+        let token_state = &mut ctx.accounts.token_state;
+
+        // CRITICAL SECURITY CHECK 1: Verify admin is calling this function
+        require!(
+            ctx.accounts.admin.key() == token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: A recovery must be pending
+        require!(
+            token_state.pending_recovery_admin != Pubkey::default(),
+            RiyalError::NoPendingRecovery
+        );
+
+        let cancelled_admin = token_state.pending_recovery_admin;
+        token_state.pending_recovery_admin = Pubkey::default();
+        token_state.recovery_executable_at = 0;
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        emit!(AdminRecoveryEvent {
+            op: AdminRecoveryEvent::OP_CANCELLED,
+            admin: token_state.admin,
+            new_admin: cancelled_admin,
+            executable_at: 0,
+            timestamp: current_timestamp,
+        });
+
+        msg!(
+            "ADMIN RECOVERY CANCELLED: Admin: {}, Cancelled new admin: {}, Timestamp: {}",
+            token_state.admin,
+            cancelled_admin,
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
+    /// Grow an existing TokenState account to the current layout (admin only)
+    /// New trailing fields are zero-initialized, which is their default value, and the
+    /// cached PDA bump is backfilled for accounts created before it was stored
+    pub fn migrate_token_state(ctx: Context<MigrateTokenState>) -> Result<()> {
+        let token_state_info = ctx.accounts.token_state.to_account_info();
+
+        // CRITICAL SECURITY CHECK 1: Verify the account is a TokenState owned by this program
+        require!(
+            token_state_info.owner == ctx.program_id,
+            RiyalError::ContractNotInitialized
+        );
+
+        let old_size = token_state_info.data_len();
+        {
+            let data = token_state_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 && data[..8] == *TokenState::DISCRIMINATOR,
+                RiyalError::ContractNotInitialized
+            );
+
+            // CRITICAL SECURITY CHECK 2: Verify admin is calling this function (admin is the first field)
+            require!(
+                data[8..40] == ctx.accounts.admin.key().to_bytes(),
+                RiyalError::UnauthorizedAdmin
+            );
+        }
+
+        if old_size < TokenState::SIZE {
+            // Top up rent for the larger account
+            let rent = Rent::get()?;
+            let required_lamports = rent.minimum_balance(TokenState::SIZE);
+            let lamports_needed = required_lamports.saturating_sub(token_state_info.lamports());
+            if lamports_needed > 0 {
+                let cpi_accounts = anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: token_state_info.clone(),
+                };
+                let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+                anchor_lang::system_program::transfer(cpi_ctx, lamports_needed)?;
+            }
+
+            token_state_info.resize(TokenState::SIZE)?;
+        }
+
+        // Backfill the cached bump used by every other instruction's PDA validation
+        let mut data = token_state_info.try_borrow_mut_data()?;
+        let mut token_state = TokenState::try_deserialize(&mut &data[..])?;
+        let old_bump = token_state.bump;
+        token_state.bump = ctx.bumps.token_state;
+        token_state.try_serialize(&mut &mut data[..])?;
+
+        msg!(
+            "TOKEN STATE MIGRATED: Admin: {}, Size: {} → {} bytes, Bump: {} → {}",
+            ctx.accounts.admin.key(),
+            old_size,
+            TokenState::SIZE,
+            old_bump,
+            token_state.bump
+        );
+
+        Ok(())
+    }
+
+    /// Grow an existing UserData account to the current layout (the user or the admin, who pays any rent top-up)
+    /// Fields appended to UserData must default to zero: the new trailing bytes are zero-filled, so e.g.
+    /// a new claimed total starts at 0. Accounts already at the current size are rejected
+    pub fn migrate_user_data(ctx: Context<MigrateUserData>, user: Pubkey) -> Result<()> {
+        let user_data_info = ctx.accounts.user_data.to_account_info();
+
+        // CRITICAL SECURITY CHECK 1: Only the user or the admin may migrate
+        require!(
+            ctx.accounts.authority.key() == user || ctx.accounts.authority.key() == ctx.accounts.token_state.admin,
+            RiyalError::UnauthorizedAdmin
+        );
+
+        // CRITICAL SECURITY CHECK 2: Verify the account is a UserData owned by this program
+        require!(
+            user_data_info.owner == ctx.program_id,
+            RiyalError::InvalidUserData
+        );
+
+        let old_size = user_data_info.data_len();
+        {
+            let data = user_data_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 && data[..8] == *UserData::DISCRIMINATOR && data[8..40] == user.to_bytes(),
+                RiyalError::InvalidUserData
+            );
+        }
+
+        // CRITICAL SECURITY CHECK 3: Guard against re-running on an up-to-date account
+        require!(
+            old_size < UserData::SIZE,
+            RiyalError::UserDataAlreadyMigrated
+        );
+
+        // Top up rent for the larger account
+        let rent = Rent::get()?;
+        let required_lamports = rent.minimum_balance(UserData::SIZE);
+        let lamports_needed = required_lamports.saturating_sub(user_data_info.lamports());
+        if lamports_needed > 0 {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: user_data_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, lamports_needed)?;
+        }
+
+        user_data_info.resize(UserData::SIZE)?;
+
+        // Zero-fill the new fields explicitly, then confirm the account parses in the current layout
+        let mut data = user_data_info.try_borrow_mut_data()?;
+        data[old_size..].fill(0);
+        let user_data = UserData::try_deserialize(&mut &data[..])?;
+        user_data.try_serialize(&mut &mut data[..])?;
+
+        msg!(
+            "USER DATA MIGRATED: User: {}, Authority: {}, Size: {} → {} bytes",
+            user,
+            ctx.accounts.authority.key(),
+            old_size,
+            UserData::SIZE
+        );
+
+        Ok(())
+    }
+}
+
+/// Reject mints whose freeze authority is not the token_state PDA, before any freeze/thaw CPI is attempted
+fn require_pda_freeze_authority(mint: &Mint, token_state: &Pubkey) -> Result<()> {
+    require!(
+        mint.freeze_authority == COption::Some(*token_state),
+        RiyalError::FreezeAuthorityNotPda
+    );
+    Ok(())
+}
+
+/// Admin-signed message format verified by process_claim
+enum ClaimMessage {
+    /// "RIYAL_CLAIM_V2" | program_id | borsh(ClaimPayload)
+    Binary,
+    /// claim_text_message(program_id, payload)
+    Text,
+    /// "RIYAL_CLAIM_RANGE_V1" | program_id | borsh(RangeClaimPayload)
+    Range(RangeClaimPayload),
+}
+
+/// Shared claim flow for claim_tokens, claim_tokens_text and claim_tokens_range
+/// `message` selects which admin-signed message format is verified
+fn process_claim(
+    ctx: Context<ClaimTokens>,
+    payload: ClaimPayload,
+    admin_signature: [u8; 64],
+    allowlist_proof: Vec<[u8; 32]>,
+    message: ClaimMessage,
+) -> Result<ClaimReceipt> {
+    let token_state = &ctx.accounts.token_state;
+    let user_data = &mut ctx.accounts.user_data;
+    let claimer = ctx.accounts.user.key();
+
+    // Reject retired instruction families
+    require_enabled!(token_state, instruction_family::CLAIM);
+
+    // Reversible pause of the signed claim path, independent of the permanent family switch
+    require_claim!(
+        !token_state.signature_claims_paused,
+        claim_reject::SIGNATURE_CLAIMS_PAUSED,
+        claimer,
+        RiyalError::SignatureClaimsPaused
+    );
+    
+    // Verify contract is initialized
+    require_claim!(
+        token_state.is_initialized,
+        claim_reject::NOT_INITIALIZED,
+        claimer,
+        RiyalError::ContractNotInitialized
+    );
+
+    // Verify token mint has been created
+    require_claim!(
+        token_state.token_mint != Pubkey::default(),
+        claim_reject::MINT_NOT_CREATED,
+        claimer,
+        RiyalError::TokenMintNotCreated
+    );
+
+    // Verify the mint account matches the stored mint
+    require_claim!(
+        ctx.accounts.mint.key() == token_state.token_mint,
+        claim_reject::INVALID_MINT,
+        claimer,
+        RiyalError::InvalidTokenMint
+    );
+
+    // Claims freeze (and may thaw) the destination, which needs the PDA as freeze authority
+    require_claim!(
+        ctx.accounts.mint.freeze_authority == COption::Some(token_state.key()),
+        claim_reject::INVALID_MINT,
+        claimer,
+        RiyalError::FreezeAuthorityNotPda
+    );
+
+    // Verify the token account is for the correct mint
+    require_claim!(
+        ctx.accounts.user_token_account.mint == token_state.token_mint,
+        claim_reject::INVALID_TOKEN_ACCOUNT,
+        claimer,
+        RiyalError::InvalidTokenAccount
+    );
+
+    // CRITICAL: This check should come FIRST
+    require_claim!(
+    payload.user_address == ctx.accounts.user.key(),
+    claim_reject::UNAUTHORIZED_DESTINATION,
+    claimer,
+    RiyalError::UnauthorizedDestination
+    );
+    // CRITICAL SECURITY: Verify destination binding - user can only claim to their own token account
+    require_claim!(
+        ctx.accounts.user_token_account.owner == ctx.accounts.user.key(),
+        claim_reject::UNAUTHORIZED_DESTINATION,
+        claimer,
+        RiyalError::UnauthorizedDestination
+    );
+
+    // Verify amount is not zero
+    require_claim!(
+        payload.claim_amount > 0,
+        claim_reject::ZERO_AMOUNT,
+        claimer,
+        RiyalError::InvalidMintAmount
+    );
+
+    // Get current timestamp and slot for validation
+    let clock = Clock::get()?;
+    let current_timestamp = clock.unix_timestamp;
+    let current_slot = clock.slot;
+
+    // Global campaign window, enforced regardless of which signatures are outstanding
+    if token_state.claim_window_open > 0 {
+        require_claim!(
+            current_timestamp >= token_state.claim_window_open,
+            claim_reject::WINDOW_NOT_OPEN,
+            claimer,
+            RiyalError::ClaimWindowNotOpen
+        );
+    }
+    if token_state.claim_window_close > 0 {
+        require_claim!(
+            current_timestamp < token_state.claim_window_close,
+            claim_reject::WINDOW_CLOSED,
+            claimer,
+            RiyalError::ClaimWindowClosed
+        );
+    }
+
+    // CRITICAL SECURITY: Validate expiry timestamp
+    // clock_skew_tolerance absorbs validator clock drift and widens the replay window by the same amount
+    require_claim!(
+        current_timestamp <= payload.expiry_time.saturating_add(token_state.clock_skew_tolerance),
+        claim_reject::EXPIRED,
+        claimer,
+        RiyalError::ClaimExpired
+    );
+
+    // Reject garbage expiries from a misbehaving signer - a sane payload expires soon
+    if token_state.max_future_expiry_seconds > 0 {
+        require_claim!(
+            payload.expiry_time <= current_timestamp.saturating_add(token_state.max_future_expiry_seconds),
+            claim_reject::EXPIRY_TOO_FAR,
+            claimer,
+            RiyalError::ExpiryTooFar
+        );
+    }
+
+    // CRITICAL SECURITY CHECK 1: Verify user data belongs to the user
+    require_claim!(
+        user_data.user == ctx.accounts.user.key(),
+        claim_reject::INVALID_USER_DATA,
+        claimer,
+        RiyalError::InvalidUserData
+    );
+
+    // CRITICAL SECURITY CHECK 2: Verify nonce matches user's current nonce (prevent replay attacks)
+    require_claim!(
+        payload.nonce == user_data.nonce,
+        claim_reject::INVALID_NONCE,
+        claimer,
+        RiyalError::InvalidNonce
+    );
+
+    // CRITICAL SECURITY CHECK 3: Reject claimers tagged with a blocked region
+    require_claim!(
+        !token_state.is_region_blocked(user_data.region),
+        claim_reject::REGION_BLOCKED,
+        claimer,
+        RiyalError::RegionBlocked
+    );
+
+    // Denylisted owners or token accounts can never receive minted tokens
+    require_claim!(
+        !token_state.is_mint_denied(&ctx.accounts.user_token_account.owner)
+            && !token_state.is_mint_denied(&ctx.accounts.user_token_account.key()),
+        claim_reject::DESTINATION_DENIED,
+        claimer,
+        RiyalError::DestinationDenied
+    );
+    if let Some(split_token_account) = ctx.accounts.split_token_account.as_ref() {
+        require_claim!(
+            !token_state.is_mint_denied(&split_token_account.owner)
+                && !token_state.is_mint_denied(&split_token_account.key()),
+            claim_reject::DESTINATION_DENIED,
+            claimer,
+            RiyalError::DestinationDenied
+        );
+    }
+
+    // Tagged claims must be counted in their campaign's SourceStats PDA
+    if payload.source != 0 {
+        require_claim!(
+            ctx.accounts.source_stats
+                .as_ref()
+                .is_some_and(|source_stats| source_stats.source == payload.source),
+            claim_reject::INVALID_SOURCE_STATS,
+            claimer,
+            RiyalError::InvalidSourceStats
+        );
+    }
+
+    // Mild sybil deterrent: the claiming wallet must hold some SOL
+    if token_state.min_claimer_sol > 0 {
+        require_claim!(
+            ctx.accounts.user.lamports() >= token_state.min_claimer_sol,
+            claim_reject::INSUFFICIENT_CLAIMER_FUNDS,
+            claimer,
+            RiyalError::InsufficientClaimerFunds
+        );
+    }
+
+    // CRITICAL SECURITY CHECK 4: Enforce the lifetime claim quota
+    // user_data is never closed, so total_claims is a lifetime counter that cannot be reset
+    if token_state.max_claims_per_user > 0 {
+        require_claim!(
+            user_data.total_claims < token_state.max_claims_per_user,
+            claim_reject::QUOTA_EXCEEDED,
+            claimer,
+            RiyalError::ClaimQuotaExceeded
+        );
+    }
+
+    // One-shot distributions: a user's first claim is also their last
+    if token_state.single_claim_mode {
+        require_claim!(
+            user_data.total_claims == 0,
+            claim_reject::ALREADY_CLAIMED,
+            claimer,
+            RiyalError::AlreadyClaimed
+        );
+    }
+
+    // CRITICAL SECURITY CHECK 5: TIME-LOCK VALIDATION - enforce claim periods
+    if token_state.time_lock_enabled {
+        // A signed bypass skips the cooldown, the next allowed time still advances from now
+        if payload.bypass_time_lock {
+            if !token_state.quiet_logging {
+                msg!(
+                    "TIME-LOCK BYPASSED: Admin-signed bypass for user: {}",
+                    ctx.accounts.user.key()
+                );
+            }
+        } else if token_state.use_slot_timelock {
+            // Slot-based cooldown - monotonic and not subject to validator clock drift
+            require_claim!(
+                current_slot >= user_data.next_allowed_claim_slot,
+                claim_reject::TIME_LOCKED,
+                claimer,
+                RiyalError::ClaimTimeLocked
+            );
+            
+            if user_data.total_claims > 0 {
+                require_claim!(
+                    current_slot >= user_data.last_claim_slot.saturating_add(token_state.claim_period_slots),
+                    claim_reject::PERIOD_NOT_ELAPSED,
+                    claimer,
+                    RiyalError::ClaimPeriodNotElapsed
+                );
+            }
+        } else {
+            // Check if enough time has passed since last claim
+            require_claim!(
+                current_timestamp.saturating_add(token_state.clock_skew_tolerance) >= user_data.next_allowed_claim_time,
+                claim_reject::TIME_LOCKED,
+                claimer,
+                RiyalError::ClaimTimeLocked
+            );
+            
+            // For first-time claims, allow immediately
+            if user_data.total_claims > 0 {
+                require_claim!(
+                    current_timestamp.saturating_add(token_state.clock_skew_tolerance) >= user_data.last_claim_timestamp
+                        .saturating_add(token_state.effective_claim_period(user_data.total_claims)),
+                    claim_reject::PERIOD_NOT_ELAPSED,
+                    claimer,
+                    RiyalError::ClaimPeriodNotElapsed
+                );
+            }
+        }
+    } else {
+        // If time-lock disabled, still enforce minimum 1 second gap
+        if user_data.last_claim_timestamp > 0 {
+            require_claim!(
+                current_timestamp > user_data.last_claim_timestamp,
+                claim_reject::TOO_FREQUENT,
+                claimer,
+                RiyalError::ClaimTooSoon
+            );
+            
+            require_claim!(
+                current_timestamp >= user_data.last_claim_timestamp.saturating_add(1),
+                claim_reject::TOO_FREQUENT,
+                claimer,
+                RiyalError::ClaimTooFrequent
+            );
+        }
+    }
+
+    // CRITICAL SECURITY CHECK 6: Validate nonce progression
+    if user_data.total_claims > 0 {
+        require_claim!(
+            payload.nonce == user_data.nonce,
+            claim_reject::INVALID_NONCE,
+            claimer,
+            RiyalError::InvalidNonceSequence
+        );
+    }
+
+    // Validate the signed split against the supplied secondary account
+    require_claim!(
+        payload.split_bps < 10000,
+        claim_reject::INVALID_SPLIT,
+        claimer,
+        RiyalError::InvalidSplit
+    );
+    if payload.split_bps > 0 {
+        require_claim!(
+            ctx.accounts.split_token_account
+                .as_ref()
+                .is_some_and(|split_account| split_account.key() == payload.split_destination),
+            claim_reject::INVALID_SPLIT,
+            claimer,
+            RiyalError::InvalidSplit
+        );
+        // Both legs into one account would double-count it against the frozen-balance cap
+        require_claim!(
+            payload.split_destination != ctx.accounts.user_token_account.key(),
+            claim_reject::INVALID_SPLIT,
+            claimer,
+            RiyalError::InvalidSplit
+        );
+    }
+
+    // CRITICAL SECURITY: Verify admin signature format
+    require_claim!(
+        admin_signature.len() == 64,
+        claim_reject::INVALID_SIGNATURE,
+        claimer,
+        RiyalError::InvalidAdminSignature
+    );
+
+    // Verify signature is not empty
+    let admin_sig_sum: u64 = admin_signature.iter().map(|&x| x as u64).sum();
+    require_claim!(
+        admin_sig_sum > 0,
+        claim_reject::INVALID_SIGNATURE,
+        claimer,
+        RiyalError::InvalidAdminSignature
+    );
+
+    // Allowlist: the claimer must prove membership in the admin-set Merkle root
+    if token_state.require_allowlist {
+        require_claim!(
+            verify_proof(&allowlist_proof, &token_state.allowlist_root, allowlist_leaf(&claimer)),
+            claim_reject::NOT_ALLOWLISTED,
+            claimer,
+            RiyalError::NotAllowlisted
+        );
+    }
+
+    // Nonce-range authorizations: the current nonce must be inside the signed range and the
+    // running total for that range (keyed by nonce_start) must stay within total_authorized
+    let range_consumed = if let ClaimMessage::Range(range_payload) = &message {
+        require_claim!(
+            range_payload.nonce_start <= payload.nonce && payload.nonce <= range_payload.nonce_end,
+            claim_reject::INVALID_NONCE,
+            claimer,
+            RiyalError::InvalidNonce
+        );
+
+        // Only one consumption slot is kept, so once a later range has been used an older one
+        // can never be reopened - otherwise interleaving two ranges would reset its total
+        require_claim!(
+            range_payload.nonce_start >= user_data.range_nonce_start,
+            claim_reject::RANGE_SUPERSEDED,
+            claimer,
+            RiyalError::RangeAuthorizationSuperseded
+        );
+
+        let already_consumed = if user_data.range_nonce_start == range_payload.nonce_start {
+            user_data.range_consumed
+        } else {
+            0
+        };
+        let consumed = already_consumed.checked_add(payload.claim_amount);
+        require_claim!(
+            consumed.is_some_and(|consumed| consumed <= range_payload.total_authorized),
+            claim_reject::RANGE_EXHAUSTED,
+            claimer,
+            RiyalError::RangeAuthorizationExhausted
+        );
+        consumed
+    } else {
+        None
+    };
+
+    // COMPUTE: every check above is a plain field or account comparison (plus a bounded
+    // Merkle proof when the allowlist is on). Rejected claims
+    // return before the payload is serialized and before the Ed25519 instruction scan
+    // (up to MAX_SCAN_INSTRUCTIONS sysvar loads), which dominate the cost of validation.
+    // Benchmark: the "Compute: Early Claim Rejection" test simulates an expired claim with and
+    // without the Ed25519 instruction and logs both CU figures - they must be equal, since
+    // neither reaches the scan. Keep new cheap precondition checks above this point.
+    let message_bytes = match &message {
+        // Human-readable, domain-separated text for hardware wallets
+        ClaimMessage::Text => claim_text_message(&crate::ID, &payload).into_bytes(),
+        ClaimMessage::Binary => {
+            // Serialize the payload to create the message that was signed by admin
+            let payload_bytes = payload.try_to_vec().map_err(|_| RiyalError::InvalidClaimPayload)?;
+
+            // Create DOMAIN-SEPARATED MESSAGE with the payload
+            // Format: "RIYAL_CLAIM_V2" | program_id | payload_bytes
+            let mut message_bytes = Vec::new();
+            message_bytes.extend_from_slice(CLAIM_MESSAGE_PREFIX);
+            message_bytes.extend_from_slice(&crate::ID.to_bytes());
+            message_bytes.extend_from_slice(&payload_bytes);
+            message_bytes
+        }
+        ClaimMessage::Range(range_payload) => {
+            // Format: "RIYAL_CLAIM_RANGE_V1" | program_id | range_payload_bytes
+            let range_bytes = range_payload.try_to_vec().map_err(|_| RiyalError::InvalidClaimPayload)?;
+
+            let mut message_bytes = Vec::new();
+            message_bytes.extend_from_slice(CLAIM_RANGE_MESSAGE_PREFIX);
+            message_bytes.extend_from_slice(&crate::ID.to_bytes());
+            message_bytes.extend_from_slice(&range_bytes);
+            message_bytes
+        }
+    };
+
+    // ENHANCED SECURITY: Verify only admin signature using Ed25519 program
+    // This requires an Ed25519 verify instruction to be included in the transaction
+    let claim_signer = verify_admin_signature_only(
+        &ctx.accounts.instructions,
+        &message_bytes,
+        &admin_signature,
+        &token_state.claim_signer_key(),
+        token_state.strict_sig_position,
+    ).inspect_err(|_| msg!("CLAIM_REJECT code={} user={}", claim_reject::INVALID_SIGNATURE, claimer))?;
+
+    if !token_state.quiet_logging {
+        msg!(
+            "REAL ED25519 VERIFICATION SUCCESS: Admin signature cryptographically verified"
+        );
+    }
+
+    // When oracle pricing is enabled the signed amount is in USD cents
+    let base_amount = if token_state.use_oracle_pricing {
+        let Some(price_oracle) = ctx.accounts.price_oracle.as_ref() else {
+            msg!("CLAIM_REJECT code={} user={}", claim_reject::ORACLE_REJECTED, claimer);
+            return err!(RiyalError::InvalidOracleAccount);
+        };
+        require_claim!(
+            price_oracle.key() == token_state.price_oracle,
+            claim_reject::ORACLE_REJECTED,
+            claimer,
+            RiyalError::InvalidOracleAccount
+        );
+        read_oracle_price(&price_oracle.to_account_info())
+            .and_then(|oracle_price| usd_cents_to_token_amount(
+                payload.claim_amount,
+                &oracle_price,
+                ctx.accounts.mint.decimals,
+                current_timestamp,
+            ))
+            .inspect_err(|_| msg!("CLAIM_REJECT code={} user={}", claim_reject::ORACLE_REJECTED, claimer))?
+    } else {
+        payload.claim_amount
+    };
+
+    // LOYALTY TIERS: the admin signs the base amount, the tier multiplier is applied on-chain.
+    // The scaled amount is what the supply cap/reserve and frozen-balance checks below see.
+    let mint_amount = u64::try_from(
+        (base_amount as u128)
+            .saturating_mul(token_state.reward_multiplier_bps(user_data.total_claims) as u128)
+            / 10000
+    ).map_err(|_| RiyalError::InvalidMintAmount)?;
+
+    // Oracle conversion and tier multipliers can round a non-zero claim down to nothing
+    require_claim!(
+        mint_amount > 0,
+        claim_reject::COMPUTED_AMOUNT_ZERO,
+        claimer,
+        RiyalError::ComputedAmountZero
+    );
+
+    // BURN-ON-CLAIM TAX: the tax is never minted - only the net amount reaches the user and split
+    // account, while the lifetime counters book it as minted and burned so they still match supply
+    let burn_amount = ((mint_amount as u128) * (token_state.claim_burn_bps as u128) / 10000) as u64;
+    let net_amount = mint_amount - burn_amount;
+
+    // Split the net claim between the user and the secondary account
+    let split_amount = ((net_amount as u128) * (payload.split_bps as u128) / 10000) as u64;
+    let user_amount = net_amount - split_amount;
+
+    // Every minted leg must receive tokens - never report a claim where one side got nothing
+    require_claim!(
+        user_amount > 0,
+        claim_reject::COMPUTED_AMOUNT_ZERO,
+        claimer,
+        RiyalError::ComputedAmountZero
+    );
+    if payload.split_bps > 0 {
+        require_claim!(
+            split_amount > 0,
+            claim_reject::COMPUTED_AMOUNT_ZERO,
+            claimer,
+            RiyalError::ComputedAmountZero
+        );
+    }
+
+    // Claims may not consume the part of the supply cap reserved for the treasury (covers both legs)
+    // The burned tax never enters supply, so only the net amount counts against the cap
+    if let Some(headroom) = token_state.user_mint_headroom(ctx.accounts.mint.supply) {
+        require_claim!(
+            net_amount <= headroom,
+            claim_reject::RESERVE_VIOLATION,
+            claimer,
+            RiyalError::ReserveViolation
+        );
+    }
+
+    // Anomaly guard: out-of-distribution amounts need an explicit signed override
+    require_claim!(
+        payload.anomaly_override || !token_state.is_anomalous_claim(mint_amount),
+        claim_reject::ANOMALOUS_CLAIM,
+        claimer,
+        RiyalError::AnomalousClaim
+    );
+
+    // Rolling per-user daily amount cap, reset when the unix day advances
+    let day_index = current_timestamp / 86400;
+    let claimed_today = if user_data.day_index == day_index {
+        user_data.amount_today
+    } else {
+        0
+    };
+    let amount_today = claimed_today
+        .checked_add(mint_amount)
+        .ok_or(RiyalError::DailyAmountCapExceeded)?;
+    if token_state.daily_amount_cap > 0 {
+        require_claim!(
+            amount_today <= token_state.daily_amount_cap,
+            claim_reject::DAILY_AMOUNT_CAP,
+            claimer,
+            RiyalError::DailyAmountCapExceeded
+        );
+    }
+
+    // Limit how much frozen balance a single account can accumulate before transfers go live
+    // Claims after permanent enablement are not frozen, so the cap no longer applies
+    if token_state.max_frozen_per_account > 0 && !token_state.transfers_permanently_enabled {
+        require_claim!(
+            ctx.accounts.user_token_account.amount
+                .checked_add(user_amount)
+                .is_some_and(|balance| balance <= token_state.max_frozen_per_account),
+            claim_reject::FROZEN_BALANCE_CAP,
+            claimer,
+            RiyalError::FrozenBalanceCapExceeded
+        );
+        if let Some(split_token_account) = ctx.accounts.split_token_account.as_ref() {
+            require_claim!(
+                split_token_account.amount
+                    .checked_add(split_amount)
+                    .is_some_and(|balance| balance <= token_state.max_frozen_per_account),
+                claim_reject::FROZEN_BALANCE_CAP,
+                claimer,
+                RiyalError::FrozenBalanceCapExceeded
+            );
+        }
+    }
+
+    // Create PDA signer for minting (using token_state as authority)
+    let seeds = &[
+        b"token_state".as_ref(),
+        &[ctx.accounts.token_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Accounts left frozen by an earlier mint must be thawed first - they are re-frozen below
+    if ctx.accounts.user_token_account.is_frozen() {
+        let thaw_cpi_accounts = ThawAccount {
+            account: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+        let thaw_cpi_ctx = CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds);
+
+        thaw_account(thaw_cpi_ctx)?;
+    }
+
+    // Create CPI context for minting with PDA as authority
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.token_state.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+    // Mint tokens first
+    mint_to(cpi_ctx, user_amount)?;
+
+    // Once transfers are permanently enabled, freezing would only create thaw churn -
+    // claimed tokens are left transferable (and any thawed account stays thawed)
+    let freeze_after_mint = !ctx.accounts.token_state.transfers_permanently_enabled;
+
+    // CRITICAL SECURITY: Immediately freeze the account after minting to prevent transfers
+    if freeze_after_mint {
+        let freeze_seeds = &[
+            b"token_state".as_ref(),
+            &[ctx.accounts.token_state.bump],
+        ];
+        let freeze_signer_seeds = &[&freeze_seeds[..]];
+
+        let freeze_cpi_accounts = FreezeAccount {
+            account: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.token_state.to_account_info(),
+        };
+        let freeze_cpi_program = ctx.accounts.token_program.to_account_info();
+        let freeze_cpi_ctx = CpiContext::new_with_signer(freeze_cpi_program, freeze_cpi_accounts, freeze_signer_seeds);
+
+        // Freeze the account immediately after claiming
+        freeze_account(freeze_cpi_ctx)?;
+    }
+
+    // Mint and freeze the split share in the same instruction
+    if split_amount > 0 {
+        if let Some(split_token_account) = ctx.accounts.split_token_account.as_ref() {
+            if split_token_account.is_frozen() {
+                let thaw_cpi_accounts = ThawAccount {
+                    account: split_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    authority: ctx.accounts.token_state.to_account_info(),
+                };
+                let thaw_cpi_program = ctx.accounts.token_program.to_account_info();
+                let thaw_cpi_ctx = CpiContext::new_with_signer(thaw_cpi_program, thaw_cpi_accounts, signer_seeds);
+
+                thaw_account(thaw_cpi_ctx)?;
+            }
+
+            let split_cpi_accounts = MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: split_token_account.to_account_info(),
+                authority: ctx.accounts.token_state.to_account_info(),
+            };
+            let split_cpi_program = ctx.accounts.token_program.to_account_info();
+            let split_cpi_ctx = CpiContext::new_with_signer(split_cpi_program, split_cpi_accounts, signer_seeds);
+
+            mint_to(split_cpi_ctx, split_amount)?;
+
+            if freeze_after_mint {
+                let split_freeze_cpi_accounts = FreezeAccount {
+                    account: split_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    authority: ctx.accounts.token_state.to_account_info(),
+                };
+                let split_freeze_cpi_program = ctx.accounts.token_program.to_account_info();
+                let split_freeze_cpi_ctx = CpiContext::new_with_signer(split_freeze_cpi_program, split_freeze_cpi_accounts, signer_seeds);
+
+                freeze_account(split_freeze_cpi_ctx)?;
+            }
+        }
+    }
+
+    // Read the post-mint supply - must happen after every mint_to CPI above
+    ctx.accounts.mint.reload()?;
+    let supply_after = ctx.accounts.mint.supply;
+
+    // ATOMICITY: mint, freeze and every user_data/token_state update below share this
+    // instruction's success path. A failed freeze CPI returns Err above, so the runtime
+    // discards the mint and the nonce is never advanced - keep all state writes after the CPIs.
+
+    // CRITICAL SECURITY UPDATE: Increment nonce and update security tracking
+    let old_nonce = user_data.nonce;
+    user_data.nonce = user_data.nonce.checked_add(1)
+        .ok_or(RiyalError::NonceOverflow)?;
+    
+    // Update timestamp and claim count for additional security tracking
+    user_data.last_claim_timestamp = current_timestamp;
+    user_data.total_claims = user_data.total_claims.checked_add(1)
+        .ok_or(RiyalError::ClaimCountOverflow)?;
+    
+    // CRITICAL TIME-LOCK UPDATE: Set next allowed claim time
+    if token_state.time_lock_enabled {
+        user_data.next_allowed_claim_time = current_timestamp
+            .checked_add(token_state.effective_claim_period(user_data.total_claims))
+            .ok_or(RiyalError::TimestampOverflow)?;
+    } else {
+        // If time-lock disabled, allow next claim after 1 second
+        user_data.next_allowed_claim_time = current_timestamp.saturating_add(1);
+    }
+
+    // Daily amount tracking is always recorded so enabling the cap takes effect immediately
+    user_data.day_index = day_index;
+    user_data.amount_today = amount_today;
+
+    // Bounds what reverse_claim may burn for this claim, and what it hands back to the daily cap
+    user_data.last_claim_amount = user_amount;
+    user_data.last_claim_daily_amount = mint_amount;
+
+    // Record consumption against the active nonce-range authorization
+    if let (ClaimMessage::Range(range_payload), Some(consumed)) = (&message, range_consumed) {
+        user_data.range_nonce_start = range_payload.nonce_start;
+        user_data.range_consumed = consumed;
+    }
+
+    // Slot tracking is always recorded so switching to slot mode has a baseline
+    user_data.last_claim_slot = current_slot;
+    if token_state.time_lock_enabled && token_state.use_slot_timelock {
+        user_data.next_allowed_claim_slot = current_slot
+            .checked_add(token_state.claim_period_slots)
+            .ok_or(RiyalError::TimestampOverflow)?;
+    }
+
+    // Track lifetime issuance in the same instruction as the mint
+    let token_state = &mut ctx.accounts.token_state;
+    token_state.lifetime_minted = token_state.lifetime_minted
+        .checked_add(mint_amount)
+        .ok_or(RiyalError::SupplyCounterOverflow)?;
+    token_state.lifetime_burned = token_state.lifetime_burned
+        .checked_add(burn_amount)
+        .ok_or(RiyalError::SupplyCounterOverflow)?;
+    token_state.record_claim_amount(mint_amount);
+
+    // Campaign attribution, validated against payload.source above
+    if payload.source != 0 {
+        if let Some(source_stats) = ctx.accounts.source_stats.as_mut() {
+            source_stats.claim_count = source_stats.claim_count.saturating_add(1);
+            source_stats.total_amount = source_stats.total_amount.saturating_add(net_amount);
+        }
+    }
+
+    if !token_state.quiet_logging {
+        msg!(
+            "CLAIM SUCCESSFUL: User: {}, Amount: {}, Split amount: {}, Nonce used: {}, New nonce: {}, Timestamp: {}, Total claims: {}",
+            ctx.accounts.user.key(),
+            user_amount,
+            split_amount,
+            old_nonce,
+            user_data.nonce,
+            current_timestamp,
+            user_data.total_claims
+        );
+        if burn_amount > 0 {
+            msg!(
+                "CLAIM TAX BURNED: User: {}, Gross: {}, Burned: {}, Rate: {} bps",
+                ctx.accounts.user.key(),
+                mint_amount,
+                burn_amount,
+                token_state.claim_burn_bps
+            );
+        }
+    }
+
+    let claim_id = claim_id(&crate::ID, &ctx.accounts.user.key(), old_nonce, payload.claim_amount);
+
+    emit!(ClaimEvent {
+        claim_id,
+        user: ctx.accounts.user.key(),
+        amount: user_amount,
+        split_amount,
+        nonce: old_nonce,
+        supply_after,
+        decimals: token_state.decimals,
+        signer: claim_signer,
+        timestamp: current_timestamp,
+    });
+
+    // OPT-IN POST-CLAIM HOOK: runs last so it only fires for a fully successful claim
+    // The hook is invoked without the token_state signer, so it can never act as mint/freeze authority,
+    // and every forwarded account is passed as a non-signer, so it never inherits the claimer's signature.
+    // Re-entering this program through the hook is rejected by the runtime (no A -> B -> A CPI).
+    // CAVEAT: user_data/token_state are only written back after this instruction returns, so a hook
+    // reading them on-chain sees the pre-claim nonce and total_claims - rely on PostClaimHookData instead.
+    if token_state.post_claim_hook != Pubkey::default() {
+        let (hook_program, hook_accounts) = ctx.remaining_accounts
+            .split_first()
+            .ok_or(RiyalError::InvalidPostClaimHook)?;
+        require!(
+            hook_program.key() == token_state.post_claim_hook && hook_program.executable,
+            RiyalError::InvalidPostClaimHook
+        );
+
+        let hook_data = PostClaimHookData {
+            user: ctx.accounts.user.key(),
+            amount: user_amount,
+            split_amount,
+            nonce: old_nonce,
+            timestamp: current_timestamp,
+        };
+        let mut data = hash(b"global:post_claim").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&hook_data.try_to_vec().map_err(|_| RiyalError::InvalidClaimPayload)?);
+
+        let hook_instruction = Instruction {
+            program_id: hook_program.key(),
+            accounts: hook_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: false,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        invoke(&hook_instruction, ctx.remaining_accounts)?;
+
+        if !token_state.quiet_logging {
+            msg!(
+                "POST-CLAIM HOOK INVOKED: Program: {}, User: {}",
+                hook_program.key(),
+                ctx.accounts.user.key()
+            );
+        }
+    }
+
+    Ok(ClaimReceipt {
+        claim_id,
+        user: ctx.accounts.user.key(),
+        amount: user_amount,
+        split_amount,
+        nonce: old_nonce,
+        supply_after,
+    })
+}
+
+/// Deterministic claim id for off-chain dedupe:
+/// `sha256("RIYAL_CLAIM_ID" | user | nonce_le | claim_amount_le | program_id)`
+/// `claim_amount` is the signed payload amount, so backends can derive it from what they signed
+pub fn claim_id(program_id: &Pubkey, user: &Pubkey, nonce: u64, claim_amount: u64) -> [u8; 32] {
+    hashv(&[
+        b"RIYAL_CLAIM_ID",
+        user.as_ref(),
+        &nonce.to_le_bytes(),
+        &claim_amount.to_le_bytes(),
+        program_id.as_ref(),
+    ]).to_bytes()
+}
+
+/// Canonical ASCII claim message for hardware-wallet signing (single line, no trailing newline):
+/// `RIYAL_CLAIM_TEXT_V1 program <program_id> | Claim <claim_amount> nonce <nonce> expiry <expiry_time> to <user_address> | bypass <true|false> split <split_bps> to <split_destination> | source <source> | override <true|false>`
+/// Amounts and timestamps are base-10 integers, pubkeys are base58
+pub fn claim_text_message(program_id: &Pubkey, payload: &ClaimPayload) -> String {
+    format!(
+        "{} program {} | Claim {} nonce {} expiry {} to {} | bypass {} split {} to {} | source {} | override {}",
+        CLAIM_TEXT_MESSAGE_PREFIX,
+        program_id,
+        payload.claim_amount,
+        payload.nonce,
+        payload.expiry_time,
+        payload.user_address,
+        payload.bypass_time_lock,
+        payload.split_bps,
+        payload.split_destination,
+        payload.source,
+        payload.anomaly_override
+    )
+}
+
+/// Descriptor of every admin-signed message format, in the field order each is signed
+pub fn message_schema() -> MessageSchema {
+    use message_field_kind::*;
+
+    fn format(instruction: &str, prefix: &[u8], encoding: u8, fields: &[(&str, u8)]) -> MessageFormat {
+        MessageFormat {
+            instruction: instruction.to_string(),
+            prefix: String::from_utf8_lossy(prefix).into_owned(),
+            encoding,
+            fields: fields
+                .iter()
+                .map(|(name, kind)| MessageField { name: name.to_string(), kind: *kind })
+                .collect(),
+        }
+    }
+
+    let claim_fields = [
+        ("user_address", PUBKEY),
+        ("claim_amount", U64),
+        ("expiry_time", I64),
+        ("nonce", U64),
+        ("bypass_time_lock", BOOL),
+        ("split_bps", U16),
+        ("split_destination", PUBKEY),
+        ("source", U16),
+        ("anomaly_override", BOOL),
+    ];
+
+    MessageSchema {
+        version: MESSAGE_SCHEMA_VERSION,
+        formats: vec![
+            format("claim_tokens", CLAIM_MESSAGE_PREFIX, message_encoding::BORSH, &claim_fields),
+            format("claim_tokens_text", CLAIM_TEXT_MESSAGE_PREFIX.as_bytes(), message_encoding::TEXT, &claim_fields),
+            format("claim_tokens_range", CLAIM_RANGE_MESSAGE_PREFIX, message_encoding::BORSH, &[
+                ("user_address", PUBKEY),
+                ("nonce_start", U64),
+                ("nonce_end", U64),
+                ("total_authorized", U64),
+                ("expiry_time", I64),
+            ]),
+            format("apply_signed_config", CONFIG_MESSAGE_PREFIX, message_encoding::BORSH, &[
+                ("nonce", U64),
+                ("expiry_time", I64),
+                ("claim_period_seconds", OPTION | I64),
+                ("time_lock_enabled", OPTION | BOOL),
+                ("claim_period_slots", OPTION | U64),
+                ("use_slot_timelock", OPTION | BOOL),
+                ("claim_period_growth_bps", OPTION | U16),
+                ("clock_skew_tolerance", OPTION | I64),
+                ("max_supply", OPTION | U64),
+                ("reserved_for_treasury", OPTION | U64),
+                ("max_claims_per_user", OPTION | U64),
+                ("max_frozen_per_account", OPTION | U64),
+                ("daily_amount_cap", OPTION | U64),
+            ]),
+            format("recover_admin", RECOVERY_MESSAGE_PREFIX, message_encoding::BORSH, &[
+                ("new_admin", PUBKEY),
+                ("recovery_nonce", U64),
+            ]),
+            format("transfer_tokens", TRANSFER_APPROVAL_MESSAGE_PREFIX, message_encoding::BORSH, &[
+                ("from", PUBKEY),
+                ("to", PUBKEY),
+                ("amount", U64),
+                ("nonce", U64),
+            ]),
+        ],
+    }
+}
+
+
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = TokenState::SIZE,
+        seeds = [b"token_state"],
+        bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTimeLock<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSlotTimeLock<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetStrictSigPosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxClaimsPerUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAnomalyMultiplier<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimBurnBps<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSingleClaimMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxFutureExpiry<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMintDenylist<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinClaimerSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSignatureClaimsPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApplySignedConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    /// Pays the transaction fee - authorization comes from the admin signature alone
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClockSkewTolerance<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDailyAmountCap<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowlistRoot<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimSigner<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRewardTiers<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetQuietLogging<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimPeriodGrowth<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxFrozenPerAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPostClaimHook<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOraclePricing<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRegionBlocked<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct SoftLockUser<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = SoftLock::SIZE,
+        seeds = [b"soft_lock", user.as_ref()],
+        bump
+    )]
+    pub soft_lock: Account<'info, SoftLock>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct SoftUnlockUser<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"soft_lock", user.as_ref()],
+        bump = soft_lock.bump
+    )]
+    pub soft_lock: Account<'info, SoftLock>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct ApproveFreeTransfer<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = FreeTransfer::SIZE,
+        seeds = [b"free_transfer", user.as_ref()],
+        bump
+    )]
+    pub free_transfer: Account<'info, FreeTransfer>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RevokeFreeTransfer<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"free_transfer", user.as_ref()],
+        bump = free_transfer.bump
+    )]
+    pub free_transfer: Account<'info, FreeTransfer>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRefreezeRecipients<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct SetUserRegion<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_data", user.as_ref()],
+        bump
+    )]
+    pub user_data: Account<'info, UserData>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[cfg(feature = "test-helpers")]
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct TestSetLastClaim<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_data", user.as_ref()],
+        bump
+    )]
+    pub user_data: Account<'info, UserData>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSupplyCap<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetBatchClaimStatus<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+}
+
+#[derive(Accounts)]
+pub struct GetSupplyStats<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct GetEffectiveCooldown<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        seeds = [b"user_data", user.as_ref()],
+        bump = user_data.bump
+    )]
+    pub user_data: Account<'info, UserData>,
+}
+
+#[derive(Accounts)]
+pub struct GetClaimSchedule<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        seeds = [b"user_data", user_data.user.as_ref()],
+        bump = user_data.bump
+    )]
+    pub user_data: Account<'info, UserData>,
+}
+
+#[derive(Accounts)]
+pub struct GetRemainingMintable<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct EnsurePdaAuthorities<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetUpgradeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = current_upgrade_authority.key() == token_state.upgrade_authority @ RiyalError::UnauthorizedUpgradeAuthority
+    )]
+    pub current_upgrade_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ValidateUpgrade<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = upgrade_authority.key() == token_state.upgrade_authority @ RiyalError::UnauthorizedUpgradeAuthority
+    )]
+    pub upgrade_authority: Signer<'info>,
+    
+    /// CHECK: Program data account for BPF loader upgradeable
+    pub program_data: UncheckedAccount<'info>,
+    
+    /// CHECK: The program being upgraded
+    #[account(executable)]
+    pub program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct UpdateTokenMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.mint_authority == COption::Some(token_state.key()) @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct CreateTokenMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = decimals,
+        mint::authority = token_state.key(),
+        mint::freeze_authority = token_state.key(),
+        mint::token_program = token_program,
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct CreateTokenMintWithTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = decimals,
+        mint::authority = token_state.key(),
+        mint::freeze_authority = token_state.key(),
+        mint::token_program = token_program,
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    // Declared after the mint so Anchor initializes the mint first
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = token_state,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    /// Lockup schedule, created when minting with a lockup (one per user token account)
+    #[account(
+        init,
+        payer = admin,
+        space = MintLockup::SIZE,
+        seeds = [b"mint_lockup", user_token_account.key().as_ref()],
+        bump
+    )]
+    pub mint_lockup: Option<Account<'info, MintLockup>>,
+    
+    /// Escrow holding the locked amount, owned and kept frozen by the token_state PDA
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = token_state,
+        seeds = [b"lockup_escrow", user_token_account.key().as_ref()],
+        bump
+    )]
+    pub lockup_escrow: Option<Account<'info, TokenAccount>>,
+    
+    pub system_program: Option<Program<'info, System>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMintLockup<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"mint_lockup", mint_lockup.beneficiary_token_account.as_ref()],
+        bump = mint_lockup.bump,
+        constraint = mint_lockup.escrow == lockup_escrow.key() @ RiyalError::InvalidMintLockup
+    )]
+    pub mint_lockup: Account<'info, MintLockup>,
+    
+    #[account(mut)]
+    pub lockup_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseTranche<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint_lockup", beneficiary_token_account.key().as_ref()],
+        bump = mint_lockup.bump,
+        has_one = beneficiary_token_account @ RiyalError::InvalidMintLockup,
+        constraint = mint_lockup.escrow == lockup_escrow.key() @ RiyalError::InvalidMintLockup
+    )]
+    pub mint_lockup: Account<'info, MintLockup>,
+    
+    #[account(
+        mut,
+        seeds = [b"lockup_escrow", beneficiary_token_account.key().as_ref()],
+        bump
+    )]
+    pub lockup_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeTokenAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UnfreezeTokenAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeUserData<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = UserData::SIZE,
+        seeds = [b"user_data", user.key().as_ref()],
+        bump
+    )]
+    pub user_data: Account<'info, UserData>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeUserDataBatch<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        mut,
+        seeds = [b"user_data", user.key().as_ref()],
+        bump
+    )]
+    pub user_data: Account<'info, UserData>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// User must sign the transaction to prove ownership
+    pub user: Signer<'info>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Price feed, only required when oracle pricing is enabled (validated against token_state)
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+
+    /// Secondary destination for split claims, must match the signed split_destination
+    #[account(
+        mut,
+        constraint = split_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub split_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// Campaign totals, required when the signed payload carries a non-zero source
+    #[account(mut)]
+    pub source_stats: Option<Account<'info, SourceStats>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTokensInit<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserData::SIZE,
+        seeds = [b"user_data", user.key().as_ref()],
+        bump
+    )]
+    pub user_data: Account<'info, UserData>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// User signs and pays for user_data creation on first claim
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Price feed, only required when oracle pricing is enabled (validated against token_state)
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+
+    /// Secondary destination for split claims, must match the signed split_destination
+    #[account(
+        mut,
+        constraint = split_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub split_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// Campaign totals, required when the signed payload carries a non-zero source
+    #[account(mut)]
+    pub source_stats: Option<Account<'info, SourceStats>>,
+}
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        constraint = user_authority.key() == user_token_account.owner @ RiyalError::UnauthorizedBurn
+    )]
+    pub user_authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct ReverseClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_data", user.as_ref()],
+        bump = user_data.bump
+    )]
+    pub user_data: Account<'info, UserData>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount,
+        constraint = user_token_account.owner == user @ RiyalError::InvalidTokenAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetReversalWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableTransfers<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LaunchTransfers<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = treasury_account.key() == token_state.treasury_account @ RiyalError::InvalidTreasuryAccount
+    )]
+    pub treasury_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UnfreezeAccount<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = user.key() == user_token_account.owner @ RiyalError::UnauthorizedUnfreeze
+    )]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TransferTokens<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = from_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub from_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = to_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub to_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = from_authority.key() == from_token_account.owner @ RiyalError::UnauthorizedTransfer
+    )]
+    pub from_authority: Signer<'info>,
+    
+    /// CHECK: Sender's user data PDA (may be uninitialized), required when regions are blocked
+    #[account(
+        seeds = [b"user_data", from_authority.key().as_ref()],
+        bump
+    )]
+    pub from_user_data: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Recipient's user data PDA (may be uninitialized), required when regions are blocked
+    #[account(
+        seeds = [b"user_data", to_token_account.owner.as_ref()],
+        bump
+    )]
+    pub to_user_data: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Sender's soft-lock PDA, only its existence is checked
+    #[account(
+        seeds = [b"soft_lock", from_authority.key().as_ref()],
+        bump
+    )]
+    pub from_soft_lock: UncheckedAccount<'info>,
+    
+    /// Sender's transfer counter, required while track_transfer_counts is on
+    #[account(
+        mut,
+        seeds = [b"transfer_stats", from_authority.key().as_ref()],
+        bump = from_transfer_stats.bump
+    )]
+    pub from_transfer_stats: Option<Account<'info, TransferStats>>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    /// CHECK: Recipient's free-transfer PDA, only its existence is checked
+    #[account(
+        seeds = [b"free_transfer", to_token_account.owner.as_ref()],
+        bump
+    )]
+    pub to_free_transfer: UncheckedAccount<'info>,
+    
+    /// Sender's approval nonce, required while require_transfer_approval is on
+    #[account(
+        mut,
+        seeds = [b"transfer_nonce", from_authority.key().as_ref()],
+        bump = from_transfer_nonce.bump
+    )]
+    pub from_transfer_nonce: Option<Account<'info, TransferNonce>>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification, required while require_transfer_approval is on
+    #[account(address = instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTransferStats<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = TransferStats::SIZE,
+        seeds = [b"transfer_stats", owner.key().as_ref()],
+        bump
+    )]
+    pub transfer_stats: Account<'info, TransferStats>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct GetTransferCount<'info> {
+    #[account(
+        seeds = [b"transfer_stats", owner.as_ref()],
+        bump = transfer_stats.bump
+    )]
+    pub transfer_stats: Account<'info, TransferStats>,
+}
+
+#[derive(Accounts)]
+#[instruction(source: u16)]
+pub struct InitializeSourceStats<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = SourceStats::SIZE,
+        seeds = [b"source_stats", source.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub source_stats: Account<'info, SourceStats>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(source: u16)]
+pub struct CloseSourceStats<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"source_stats", source.to_le_bytes().as_ref()],
+        bump = source_stats.bump
+    )]
+    pub source_stats: Account<'info, SourceStats>,
+    
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
 }
 
-
+#[derive(Accounts)]
+#[instruction(source: u16)]
+pub struct GetSourceStats<'info> {
+    #[account(
+        seeds = [b"source_stats", source.to_le_bytes().as_ref()],
+        bump = source_stats.bump
+    )]
+    pub source_stats: Account<'info, SourceStats>,
+}
 
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+pub struct InitializeTransferNonce<'info> {
     #[account(
         init,
-        payer = payer,
-        space = TokenState::SIZE,
-        seeds = [b"token_state"],
+        payer = owner,
+        space = TransferNonce::SIZE,
+        seeds = [b"transfer_nonce", owner.key().as_ref()],
         bump
     )]
-    pub token_state: Account<'info, TokenState>,
+    pub transfer_nonce: Account<'info, TransferNonce>,
     
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateTimeLock<'info> {
+pub struct SetRequireTransferApproval<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
@@ -1411,96 +8128,143 @@ pub struct UpdateTimeLock<'info> {
 }
 
 #[derive(Accounts)]
-pub struct SetUpgradeAuthority<'info> {
+pub struct SetTrackTransferCounts<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
-        constraint = current_upgrade_authority.key() == token_state.upgrade_authority @ RiyalError::UnauthorizedUpgradeAuthority
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
-    pub current_upgrade_authority: Signer<'info>,
+    pub admin: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ValidateUpgrade<'info> {
+pub struct UnfreezeAndTransfer<'info> {
     #[account(
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
-        constraint = upgrade_authority.key() == token_state.upgrade_authority @ RiyalError::UnauthorizedUpgradeAuthority
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
     )]
-    pub upgrade_authority: Signer<'info>,
+    pub mint: Account<'info, Mint>,
     
-    /// CHECK: Program data account for BPF loader upgradeable
-    pub program_data: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = from_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub from_token_account: Account<'info, TokenAccount>,
     
-    /// CHECK: The program being upgraded
-    #[account(executable)]
-    pub program: UncheckedAccount<'info>,
-}
-
-#[derive(Accounts)]
-#[instruction(decimals: u8)]
-pub struct UpdateTokenMint<'info> {
     #[account(
         mut,
-        seeds = [b"token_state"],
+        constraint = to_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+    )]
+    pub to_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = from_authority.key() == from_token_account.owner @ RiyalError::UnauthorizedTransfer
+    )]
+    pub from_authority: Signer<'info>,
+    
+    /// CHECK: Sender's user data PDA (may be uninitialized), required when regions are blocked
+    #[account(
+        seeds = [b"user_data", from_authority.key().as_ref()],
         bump
     )]
-    pub token_state: Account<'info, TokenState>,
+    pub from_user_data: Option<UncheckedAccount<'info>>,
     
+    /// CHECK: Recipient's user data PDA (may be uninitialized), required when regions are blocked
     #[account(
-        constraint = mint.mint_authority == COption::Some(token_state.key()) @ RiyalError::InvalidTokenMint
+        seeds = [b"user_data", to_token_account.owner.as_ref()],
+        bump
     )]
-    pub mint: Account<'info, Mint>,
+    pub to_user_data: Option<UncheckedAccount<'info>>,
     
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    /// CHECK: Sender's soft-lock PDA, only its existence is checked
+    #[account(
+        seeds = [b"soft_lock", from_authority.key().as_ref()],
+        bump
+    )]
+    pub from_soft_lock: UncheckedAccount<'info>,
     
     pub token_program: Program<'info, Token>,
+    
+    /// Sender's transfer counter, required while track_transfer_counts is on
+    #[account(
+        mut,
+        seeds = [b"transfer_stats", from_authority.key().as_ref()],
+        bump = from_transfer_stats.bump
+    )]
+    pub from_transfer_stats: Option<Account<'info, TransferStats>>,
 }
 
 #[derive(Accounts)]
-#[instruction(decimals: u8)]
-pub struct CreateTokenMint<'info> {
+pub struct CreateTreasury<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
         init,
         payer = admin,
-        mint::decimals = decimals,
-        mint::authority = token_state.key(),
-        mint::freeze_authority = token_state.key(),
-        mint::token_program = token_program,
+        associated_token::mint = mint,
+        associated_token::authority = token_state,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
     )]
     pub mint: Account<'info, Mint>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
     pub admin: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct MintTokens<'info> {
+pub struct RegisterTreasury<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = treasury_account.mint == token_state.token_mint @ RiyalError::TreasuryMintMismatch,
+        constraint = treasury_account.owner == token_state.key() @ RiyalError::InvalidTreasuryAuthority
+    )]
+    pub treasury_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MintToTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
@@ -1512,12 +8276,12 @@ pub struct MintTokens<'info> {
     
     #[account(
         mut,
-        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+        constraint = treasury_account.key() == token_state.treasury_account @ RiyalError::InvalidTreasuryAccount,
+        constraint = treasury_account.mint == token_state.token_mint @ RiyalError::TreasuryMintMismatch
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub treasury_account: Account<'info, TokenAccount>,
     
     #[account(
-        mut,
         constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
     pub admin: Signer<'info>,
@@ -1526,11 +8290,11 @@ pub struct MintTokens<'info> {
 }
 
 #[derive(Accounts)]
-pub struct FreezeTokenAccount<'info> {
+pub struct BurnFromTreasury<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
@@ -1542,12 +8306,12 @@ pub struct FreezeTokenAccount<'info> {
     
     #[account(
         mut,
-        constraint = token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+        constraint = treasury_account.key() == token_state.treasury_account @ RiyalError::InvalidTreasuryAccount,
+        constraint = treasury_account.mint == token_state.token_mint @ RiyalError::TreasuryMintMismatch
     )]
-    pub token_account: Account<'info, TokenAccount>,
+    pub treasury_account: Account<'info, TokenAccount>,
     
     #[account(
-        mut,
         constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
     pub admin: Signer<'info>,
@@ -1556,28 +8320,30 @@ pub struct FreezeTokenAccount<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UnfreezeTokenAccount<'info> {
+#[instruction(new_treasury: Pubkey)]
+pub struct SetTreasury<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
         mut,
-        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+        constraint = old_treasury_account.key() == token_state.treasury_account @ RiyalError::InvalidTreasuryAccount
     )]
-    pub mint: Account<'info, Mint>,
+    pub old_treasury_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+        constraint = new_treasury_account.key() == new_treasury @ RiyalError::InvalidTreasuryAccount,
+        constraint = new_treasury_account.mint == token_state.token_mint @ RiyalError::TreasuryMintMismatch,
+        constraint = new_treasury_account.owner == token_state.key() @ RiyalError::InvalidTreasuryAuthority
     )]
-    pub token_account: Account<'info, TokenAccount>,
+    pub new_treasury_account: Account<'info, TokenAccount>,
     
     #[account(
-        mut,
         constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
     pub admin: Signer<'info>,
@@ -1586,77 +8352,83 @@ pub struct UnfreezeTokenAccount<'info> {
 }
 
 #[derive(Accounts)]
-pub struct InitializeUserData<'info> {
+#[instruction(decimals: u8)]
+pub struct AddManagedMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
     #[account(
         init,
-        payer = user,
-        space = UserData::SIZE,
-        seeds = [b"user_data", user.key().as_ref()],
-        bump
+        payer = admin,
+        mint::decimals = decimals,
+        mint::authority = token_state.key(),
+        mint::freeze_authority = token_state.key(),
+        mint::token_program = token_program,
     )]
-    pub user_data: Account<'info, UserData>,
+    pub mint: Account<'info, Mint>,
     
-    #[account(mut)]
-    pub user: Signer<'info>,
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
     
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimTokens<'info> {
+#[instruction(mint_index: u8)]
+pub struct MintManagedTokens<'info> {
     #[account(
-        mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
-
-    #[account(
-        mut,
-        seeds = [b"user_data", user.key().as_ref()],
-        bump
-    )]
-    pub user_data: Account<'info, UserData>,
-
+    
     #[account(
         mut,
-        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+        constraint = token_state.managed_mint(mint_index) == Some(mint.key()) @ RiyalError::InvalidTokenMint
     )]
     pub mint: Account<'info, Mint>,
-
+    
     #[account(
         mut,
-        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+        constraint = user_token_account.mint == mint.key() @ RiyalError::InvalidTokenAccount
     )]
     pub user_token_account: Account<'info, TokenAccount>,
-
-    /// User must sign the transaction to prove ownership
-    pub user: Signer<'info>,
-
-    /// CHECK: Instructions sysvar for Ed25519 signature verification
-    #[account(address = instructions::ID)]
-    pub instructions: UncheckedAccount<'info>,
-
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct BurnTokens<'info> {
+#[instruction(mint_index: u8)]
+pub struct BurnManagedTokens<'info> {
     #[account(
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
         mut,
-        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+        constraint = token_state.managed_mint(mint_index) == Some(mint.key()) @ RiyalError::InvalidTokenMint
     )]
     pub mint: Account<'info, Mint>,
     
     #[account(
         mut,
-        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+        constraint = user_token_account.mint == mint.key() @ RiyalError::InvalidTokenAccount
     )]
     pub user_token_account: Account<'info, TokenAccount>,
     
@@ -1674,178 +8446,289 @@ pub struct BurnTokens<'info> {
 }
 
 #[derive(Accounts)]
-pub struct EnableTransfers<'info> {
+#[instruction(mint_index: u8)]
+pub struct FreezeManagedAccount<'info> {
     #[account(
-        mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
+    #[account(
+        constraint = token_state.managed_mint(mint_index) == Some(mint.key()) @ RiyalError::InvalidTokenMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ RiyalError::InvalidTokenAccount
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
     #[account(
         constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
     pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct UnfreezeAccount<'info> {
+#[instruction(mint_index: u8)]
+pub struct UnfreezeManagedAccount<'info> {
     #[account(
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
-        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+        constraint = token_state.managed_mint(mint_index) == Some(mint.key()) @ RiyalError::InvalidTokenMint
     )]
     pub mint: Account<'info, Mint>,
     
     #[account(
         mut,
-        constraint = user_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+        constraint = token_account.mint == mint.key() @ RiyalError::InvalidTokenAccount
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub token_account: Account<'info, TokenAccount>,
     
     #[account(
-        constraint = user.key() == user_token_account.owner @ RiyalError::UnauthorizedUnfreeze
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
-    pub user: Signer<'info>,
+    pub admin: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct TransferTokens<'info> {
+pub struct RecoverAdmin<'info> {
     #[account(
+        mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
+    /// Pays the transaction fee - authorization comes from the recovery signatures alone
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeAdminRecovery<'info> {
     #[account(
-        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
     )]
-    pub mint: Account<'info, Mint>,
+    pub token_state: Account<'info, TokenState>,
+    
+    pub relayer: Signer<'info>,
     
+    // Required once token_state.admin_history_enabled is set
     #[account(
         mut,
-        constraint = from_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+        seeds = [b"admin_history"],
+        bump = admin_history.bump
     )]
-    pub from_token_account: Account<'info, TokenAccount>,
-    
+    pub admin_history: Option<Account<'info, AdminHistory>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAdminHistory<'info> {
     #[account(
         mut,
-        constraint = to_token_account.mint == token_state.token_mint @ RiyalError::InvalidTokenAccount
+        seeds = [b"token_state"],
+        bump = token_state.bump
     )]
-    pub to_token_account: Account<'info, TokenAccount>,
+    pub token_state: Account<'info, TokenState>,
     
     #[account(
-        constraint = from_authority.key() == from_token_account.owner @ RiyalError::UnauthorizedTransfer
+        init,
+        payer = admin,
+        space = AdminHistory::SIZE,
+        seeds = [b"admin_history"],
+        bump
     )]
-    pub from_authority: Signer<'info>,
+    pub admin_history: Account<'info, AdminHistory>,
     
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetAdminHistory<'info> {
+    #[account(
+        seeds = [b"admin_history"],
+        bump = admin_history.bump
+    )]
+    pub admin_history: Account<'info, AdminHistory>,
 }
 
 #[derive(Accounts)]
-pub struct CreateTreasury<'info> {
+pub struct CancelAdminRecovery<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
-        init,
-        payer = admin,
-        associated_token::mint = mint,
-        associated_token::authority = token_state,
-        associated_token::token_program = token_program,
-    )]
-    pub treasury_account: Account<'info, TokenAccount>,
-    
-    #[account(
-        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
-    pub mint: Account<'info, Mint>,
-    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTokenState<'info> {
+    /// CHECK: Deserialized manually since an older layout may not match TokenState
     #[account(
         mut,
-        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+        seeds = [b"token_state"],
+        bump
     )]
+    pub token_state: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub admin: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct MintToTreasury<'info> {
+#[instruction(user: Pubkey)]
+pub struct MigrateUserData<'info> {
     #[account(
-        mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
+    /// CHECK: Deserialized manually since an older layout may not match UserData
     #[account(
         mut,
-        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+        seeds = [b"user_data", user.as_ref()],
+        bump
     )]
-    pub mint: Account<'info, Mint>,
+    pub user_data: UncheckedAccount<'info>,
     
+    /// The user or the admin, pays any rent top-up
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DisableInstruction<'info> {
     #[account(
         mut,
-        constraint = treasury_account.key() == token_state.treasury_account @ RiyalError::InvalidTreasuryAccount
+        seeds = [b"token_state"],
+        bump = token_state.bump
     )]
-    pub treasury_account: Account<'info, TokenAccount>,
+    pub token_state: Account<'info, TokenState>,
     
     #[account(
         constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
     pub admin: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct BurnFromTreasury<'info> {
+pub struct LockMetadata<'info> {
     #[account(
+        mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
-        mut,
-        constraint = mint.key() == token_state.token_mint @ RiyalError::InvalidTokenMint
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
-    pub mint: Account<'info, Mint>,
-    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ForbidSelfMint<'info> {
     #[account(
         mut,
-        constraint = treasury_account.key() == token_state.treasury_account @ RiyalError::InvalidTreasuryAccount
+        seeds = [b"token_state"],
+        bump = token_state.bump
     )]
-    pub treasury_account: Account<'info, TokenAccount>,
+    pub token_state: Account<'info, TokenState>,
     
     #[account(
         constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
     )]
     pub admin: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
 pub struct CheckTransfersEnabled<'info> {
     #[account(
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+}
+
+#[derive(Accounts)]
+pub struct GetMessageSchema {}
+
+#[derive(Accounts)]
+pub struct GetCapabilities<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyMintBinding<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    /// Deliberately unconstrained - a mismatch is reported, not rejected
+    pub mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct SecondsSinceTransfersEnabled<'info> {
+    #[account(
+        seeds = [b"token_state"],
+        bump = token_state.bump
+    )]
+    pub token_state: Account<'info, TokenState>,
+}
+
+#[derive(Accounts)]
+pub struct ScheduleTransferEnable<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_state"],
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
+    
+    #[account(
+        constraint = admin.key() == token_state.admin @ RiyalError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -1853,7 +8736,7 @@ pub struct PauseTransfers<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
@@ -1868,7 +8751,7 @@ pub struct ResumeTransfers<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
@@ -1883,7 +8766,7 @@ pub struct PermanentlyEnableTransfers<'info> {
     #[account(
         mut,
         seeds = [b"token_state"],
-        bump
+        bump = token_state.bump
     )]
     pub token_state: Account<'info, TokenState>,
     
@@ -1910,6 +8793,63 @@ pub struct TokenState {
     pub token_symbol: String,             // 4 + up to 16 bytes
     pub decimals: u8,                     // 1 byte
     pub bump: u8,                         // 1 byte
+    pub strict_sig_position: bool,        // 1 byte - Ed25519 verify must immediately precede the claim
+    pub price_oracle: Pubkey,             // 32 bytes - Price feed used for USD-denominated claims
+    pub use_oracle_pricing: bool,         // 1 byte - Whether claim amounts are USD cents
+    pub lifetime_minted: u64,             // 8 bytes - Total tokens minted by this program
+    pub lifetime_burned: u64,             // 8 bytes - Total tokens burned by this program
+    pub additional_mints: Vec<Pubkey>,    // 4 + up to 4 * 32 bytes - Extra mints sharing this admin/config
+    pub blocked_regions: [u8; 32],        // 32 bytes - Bitmap of blocked jurisdiction tags (256 regions)
+    pub max_supply: u64,                  // 8 bytes - Supply cap for the primary mint (0 = uncapped)
+    pub reserved_for_treasury: u64,       // 8 bytes - Part of max_supply only the treasury may mint
+    pub metadata_locked: bool,            // 1 byte - Once true, name/symbol can never change
+    pub use_slot_timelock: bool,          // 1 byte - Enforce the time-lock in slots instead of seconds
+    pub claim_period_slots: u64,          // 8 bytes - Slots between claims when slot time-lock is used
+    pub config_change_cooldown: i64,      // 8 bytes - Minimum seconds between admin config changes
+    pub last_config_change: i64,          // 8 bytes - Unix timestamp of last admin config change
+    pub post_claim_hook: Pubkey,          // 32 bytes - Program invoked after each claim (default = disabled)
+    pub max_claims_per_user: u64,         // 8 bytes - Lifetime claim quota per user (0 = unlimited)
+    pub max_frozen_per_account: u64,      // 8 bytes - Max frozen balance a claim may build up (0 = unlimited)
+    pub claim_period_growth_bps: u16,     // 2 bytes - Cooldown growth per prior claim in basis points (0 = flat)
+    pub quiet_logging: bool,              // 1 byte - Suppress informational success logs (events still emitted)
+    pub reward_tiers: Vec<RewardTier>,    // 4 + up to 8 * 10 bytes - Claim-count loyalty multipliers
+    pub claim_signer: Pubkey,             // 32 bytes - Hot key signing claims (default = admin)
+    pub treasury_minted_total: u64,       // 8 bytes - Net tokens minted to the treasury (mints minus treasury burns)
+    pub allowlist_root: [u8; 32],         // 32 bytes - Merkle root of allowlisted claimers
+    pub require_allowlist: bool,          // 1 byte - Require a Merkle allowlist proof on every claim
+    pub daily_amount_cap: u64,            // 8 bytes - Max tokens per user per UTC day (0 = unlimited)
+    pub disabled_instructions: u64,       // 8 bytes - Permanently disabled instruction_family bits
+    pub clock_skew_tolerance: i64,        // 8 bytes - Seconds of clock drift tolerated in expiry/time-lock checks
+    pub signature_claims_paused: bool,    // 1 byte - Reversible pause of the admin-signed claim path
+    pub min_claimer_sol: u64,             // 8 bytes - Minimum claimer wallet lamports (0 = disabled)
+    pub self_mint_forbidden: bool,        // 1 byte - Once true, admin mints can never target admin-owned accounts
+    pub mint_denylist: Vec<Pubkey>,       // 4 + up to 16 * 32 bytes - Owners/accounts no mint may target
+    pub config_nonce: u64,                // 8 bytes - Next nonce accepted by apply_signed_config
+    pub max_future_expiry_seconds: i64,   // 8 bytes - Max seconds a claim expiry may lie ahead (0 = unlimited)
+    pub track_transfer_counts: bool,      // 1 byte - Require and bump the sender's TransferStats PDA on transfers
+    pub single_claim_mode: bool,          // 1 byte - Reject any claim once a user has claimed once
+    pub refreeze_recipients: bool,        // 1 byte - Freeze unapproved transfer recipients until transfers are permanent
+    pub reversal_window_seconds: i64,     // 8 bytes - Seconds after a claim reverse_claim may undo it (0 = disabled)
+    pub transfers_enable_at: i64,         // 8 bytes - Scheduled transfer enable time (0 = none)
+    pub claim_window_open: i64,           // 8 bytes - Claims rejected before this time (0 = unbounded)
+    pub claim_window_close: i64,          // 8 bytes - Claims rejected from this time on (0 = unbounded)
+    pub recovery_signers: Vec<Pubkey>,    // 4 + up to 5 * 32 bytes - Keys that can jointly replace a lost admin
+    pub recovery_threshold: u8,           // 1 byte - Recovery signatures required (0 = recovery disabled)
+    pub recovery_nonce: u64,              // 8 bytes - Bound into recovery messages, bumped per initiated recovery
+    pub pending_recovery_admin: Pubkey,   // 32 bytes - Admin taking over once the timelock passes (default = none)
+    pub recovery_executable_at: i64,      // 8 bytes - When the pending recovery may be finalized
+    pub admin_history_enabled: bool,      // 1 byte - AdminHistory PDA exists and must record every admin change
+    pub require_transfer_approval: bool,  // 1 byte - Every transfer_tokens needs a fresh admin signature
+    pub anomaly_multiplier: u16,          // 2 bytes - Reject claims above this multiple of claim_amount_ewma (0 = off)
+    pub claim_amount_ewma: u64,           // 8 bytes - Exponentially weighted average claim amount
+    pub claim_burn_bps: u16,              // 2 bytes - Share of each claim burned as tax instead of minted (0 = off)
+}
+
+/// Loyalty tier: claims made once a user has `min_claims` prior claims are scaled by `multiplier_bps`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RewardTier {
+    pub min_claims: u64,
+    pub multiplier_bps: u16,
 }
 
 impl TokenState {
@@ -1928,9 +8868,340 @@ impl TokenState {
         4 + 32 +                          // token_name (String with max 32 chars)
         4 + 16 +                          // token_symbol (String with max 16 chars)
         1 +                               // decimals
-        1;                                // bump
+        1 +                               // bump
+        1 +                               // strict_sig_position
+        32 +                              // price_oracle
+        1 +                               // use_oracle_pricing
+        8 +                               // lifetime_minted
+        8 +                               // lifetime_burned
+        4 + 32 * Self::MAX_ADDITIONAL_MINTS + // additional_mints (Vec with max 4 entries)
+        32 +                              // blocked_regions
+        8 +                               // max_supply
+        8 +                               // reserved_for_treasury
+        1 +                               // metadata_locked
+        1 +                               // use_slot_timelock
+        8 +                               // claim_period_slots
+        8 +                               // config_change_cooldown
+        8 +                               // last_config_change
+        32 +                              // post_claim_hook
+        8 +                               // max_claims_per_user
+        8 +                               // max_frozen_per_account
+        2 +                               // claim_period_growth_bps
+        1 +                               // quiet_logging
+        4 + 10 * Self::MAX_REWARD_TIERS + // reward_tiers (Vec with max 8 entries)
+        32 +                              // claim_signer
+        8 +                               // treasury_minted_total
+        32 +                              // allowlist_root
+        1 +                               // require_allowlist
+        8 +                               // daily_amount_cap
+        8 +                               // disabled_instructions
+        8 +                               // clock_skew_tolerance
+        1 +                               // signature_claims_paused
+        8 +                               // min_claimer_sol
+        1 +                               // self_mint_forbidden
+        4 + 32 * Self::MAX_MINT_DENYLIST + // mint_denylist (Vec with max 16 entries)
+        8 +                               // config_nonce
+        8 +                               // max_future_expiry_seconds
+        1 +                               // track_transfer_counts
+        1 +                               // single_claim_mode
+        1 +                               // refreeze_recipients
+        8 +                               // reversal_window_seconds
+        8 +                               // transfers_enable_at
+        8 +                               // claim_window_open
+        8 +                               // claim_window_close
+        4 + 32 * Self::MAX_RECOVERY_SIGNERS + // recovery_signers (Vec with max 5 entries)
+        1 +                               // recovery_threshold
+        8 +                               // recovery_nonce
+        32 +                              // pending_recovery_admin
+        8 +                               // recovery_executable_at
+        1 +                               // admin_history_enabled
+        1 +                               // require_transfer_approval
+        2 +                               // anomaly_multiplier
+        8 +                               // claim_amount_ewma
+        2;                                // claim_burn_bps
+
+    /// Layout version reported by get_capabilities - bump whenever fields are appended
+    /// v2: fields appended after config_nonce, through claim_burn_bps
+    pub const STATE_VERSION: u16 = 2;
+
+    /// TokenState::SIZE at STATE_VERSION; the assert below fails the build on an append without a bump
+    const SIZE_AT_STATE_VERSION: usize = 1507;
+
+    /// Maximum number of mints managed in addition to the primary token_mint
+    pub const MAX_ADDITIONAL_MINTS: usize = 4;
+
+    /// Maximum number of loyalty reward tiers
+    pub const MAX_REWARD_TIERS: usize = 8;
+
+    /// Maximum number of denied mint destinations
+    pub const MAX_MINT_DENYLIST: usize = 16;
+
+    /// Maximum token_name length in bytes, matching the space reserved in SIZE
+    pub const MAX_TOKEN_NAME_LEN: usize = 32;
+
+    /// Maximum token_symbol length in bytes, matching the space reserved in SIZE
+    pub const MAX_TOKEN_SYMBOL_LEN: usize = 16;
+
+    /// Maximum number of admin recovery signers
+    pub const MAX_RECOVERY_SIGNERS: usize = 5;
+
+    /// Mandatory delay between initiating and finalizing an admin recovery - 14 days
+    pub const RECOVERY_TIMELOCK_SECONDS: i64 = 14 * 86_400;
+
+    /// Upper bound for a single tier multiplier - 6x (fits u16)
+    pub const MAX_REWARD_MULTIPLIER_BPS: u16 = 60000;
+
+    /// Key whose Ed25519 signature authorizes claims - the admin unless a claim signer is set
+    pub fn claim_signer_key(&self) -> Pubkey {
+        if self.claim_signer == Pubkey::default() {
+            self.admin
+        } else {
+            self.claim_signer
+        }
+    }
+
+    /// Multiplier for a user with `total_claims` prior claims (10000 = 1x when no tier applies)
+    pub fn reward_multiplier_bps(&self, total_claims: u64) -> u16 {
+        self.reward_tiers
+            .iter()
+            .rev()
+            .find(|tier| total_claims >= tier.min_claims)
+            .map_or(10000, |tier| tier.multiplier_bps)
+    }
+
+    /// Validate a ConfigUpdate against the resulting config as a whole, then apply it
+    /// Shared by update_config and apply_signed_config - nothing is written unless every check passes
+    pub fn apply_config_update(&mut self, config: &ConfigUpdate, mint_supply: u64) -> Result<()> {
+        // Resulting config: provided values, otherwise the current ones
+        let claim_period_seconds = config.claim_period_seconds.unwrap_or(self.claim_period_seconds);
+        let time_lock_enabled = config.time_lock_enabled.unwrap_or(self.time_lock_enabled);
+        let claim_period_slots = config.claim_period_slots.unwrap_or(self.claim_period_slots);
+        let use_slot_timelock = config.use_slot_timelock.unwrap_or(self.use_slot_timelock);
+        let claim_period_growth_bps = config.claim_period_growth_bps.unwrap_or(self.claim_period_growth_bps);
+        let clock_skew_tolerance = config.clock_skew_tolerance.unwrap_or(self.clock_skew_tolerance);
+        let max_supply = config.max_supply.unwrap_or(self.max_supply);
+        let reserved_for_treasury = config.reserved_for_treasury.unwrap_or(self.reserved_for_treasury);
+        let max_claims_per_user = config.max_claims_per_user.unwrap_or(self.max_claims_per_user);
+        let max_frozen_per_account = config.max_frozen_per_account.unwrap_or(self.max_frozen_per_account);
+        let daily_amount_cap = config.daily_amount_cap.unwrap_or(self.daily_amount_cap);
+
+        // Per-field bounds, same as the dedicated setters
+        if config.claim_period_seconds.is_some() {
+            require!(
+                (3600..=31536000).contains(&claim_period_seconds), // 1 hour to 1 year
+                RiyalError::InvalidClaimPeriod
+            );
+        }
+        if config.claim_period_slots.is_some() {
+            require!(
+                (75..=78_840_000).contains(&claim_period_slots), // ~30 seconds to ~1 year
+                RiyalError::InvalidClaimPeriod
+            );
+        }
+        require!(
+            (0..=TokenState::MAX_CLOCK_SKEW_TOLERANCE).contains(&clock_skew_tolerance),
+            RiyalError::InvalidClockSkewTolerance
+        );
+        if max_supply > 0 {
+            require!(
+                max_supply >= mint_supply,
+                RiyalError::InvalidSupplyCap
+            );
+        }
+
+        // The combination must be coherent
+        // The treasury reserve must fit inside the cap (and is meaningless without one)
+        require!(
+            if max_supply > 0 { reserved_for_treasury <= max_supply } else { reserved_for_treasury == 0 },
+            RiyalError::InconsistentConfig
+        );
+        // Slot mode needs a real slot period to enforce
+        require!(
+            !(time_lock_enabled && use_slot_timelock) || claim_period_slots >= 75,
+            RiyalError::InconsistentConfig
+        );
+        // Skew tolerance must stay well below the cooldown it relaxes
+        require!(
+            !time_lock_enabled || use_slot_timelock || clock_skew_tolerance < claim_period_seconds,
+            RiyalError::InconsistentConfig
+        );
+
+        self.claim_period_seconds = claim_period_seconds;
+        self.time_lock_enabled = time_lock_enabled;
+        self.claim_period_slots = claim_period_slots;
+        self.use_slot_timelock = use_slot_timelock;
+        self.claim_period_growth_bps = claim_period_growth_bps;
+        self.clock_skew_tolerance = clock_skew_tolerance;
+        self.max_supply = max_supply;
+        self.reserved_for_treasury = reserved_for_treasury;
+        self.max_claims_per_user = max_claims_per_user;
+        self.max_frozen_per_account = max_frozen_per_account;
+        self.daily_amount_cap = daily_amount_cap;
+
+        Ok(())
+    }
+
+    /// One-line rendering of the fields managed by ConfigUpdate, for admin logs
+    pub fn config_summary(&self) -> String {
+        format!(
+            "Period: {}s (enabled: {}), Slots: {} (slot mode: {}), Growth: {} bps, Skew: {}s, Max supply: {}, Reserved: {}, Max claims: {}, Max frozen: {}, Daily cap: {}",
+            self.claim_period_seconds,
+            self.time_lock_enabled,
+            self.claim_period_slots,
+            self.use_slot_timelock,
+            self.claim_period_growth_bps,
+            self.clock_skew_tolerance,
+            self.max_supply,
+            self.reserved_for_treasury,
+            self.max_claims_per_user,
+            self.max_frozen_per_account,
+            self.daily_amount_cap
+        )
+    }
+
+    /// Whether a mint destination (token account owner or account key) is denylisted
+    pub fn is_mint_denied(&self, destination: &Pubkey) -> bool {
+        self.mint_denylist.contains(destination)
+    }
+
+    /// Resolve a managed mint by index - index 0 is always the primary token_mint
+    pub fn managed_mint(&self, mint_index: u8) -> Option<Pubkey> {
+        match mint_index {
+            0 => Some(self.token_mint),
+            index => self.additional_mints.get(index as usize - 1).copied(),
+        }
+    }
+
+    /// Number of jurisdiction tags representable in the blocked_regions bitmap
+    pub const MAX_REGIONS: u16 = 256;
+
+    /// Check whether a jurisdiction tag is blocked - region 0 (untagged) is never blocked
+    pub fn is_region_blocked(&self, region: u16) -> bool {
+        if region == 0 || region >= Self::MAX_REGIONS {
+            return false;
+        }
+        self.blocked_regions[(region / 8) as usize] & (1 << (region % 8)) != 0
+    }
+
+    /// Supply still mintable to users while keeping the treasury reserve intact (None when uncapped)
+    /// Treasury mints already count toward current_supply, so only the unminted part of the reserve is held back
+    pub fn user_mint_headroom(&self, current_supply: u64) -> Option<u64> {
+        if self.max_supply == 0 {
+            return None;
+        }
+        Some(
+            self.max_supply
+                .saturating_sub(self.reserved_for_treasury.saturating_sub(self.treasury_minted_total))
+                .saturating_sub(current_supply)
+        )
+    }
+
+    /// Upper bound for clock_skew_tolerance - 2 minutes
+    pub const MAX_CLOCK_SKEW_TOLERANCE: i64 = 120;
+
+    /// Weight of the newest claim in claim_amount_ewma: avg += (amount - avg) / ANOMALY_EWMA_DIVISOR
+    pub const ANOMALY_EWMA_DIVISOR: u128 = 8;
+
+    /// Upper bound for reversal_window_seconds - 1 day
+    pub const MAX_REVERSAL_WINDOW_SECONDS: i64 = 86_400;
+
+    /// Upper bound for the (escalated) claim period - 1 year
+    pub const MAX_CLAIM_PERIOD_SECONDS: i64 = 31536000;
+
+    /// Claim period after `total_claims` claims, escalated by claim_period_growth_bps per claim
+    pub fn effective_claim_period(&self, total_claims: u64) -> i64 {
+        let multiplier_bps = 10000u128
+            .saturating_add((self.claim_period_growth_bps as u128).saturating_mul(total_claims as u128));
+        let period = (self.claim_period_seconds.max(0) as u128)
+            .saturating_mul(multiplier_bps)
+            / 10000;
+        period.min(Self::MAX_CLAIM_PERIOD_SECONDS as u128) as i64
+    }
+
+    /// Enforce the minimum time between admin config changes and record this change
+    pub fn record_config_change(&mut self, current_timestamp: i64) -> Result<()> {
+        require!(
+            self.last_config_change == 0
+                || current_timestamp >= self.last_config_change.saturating_add(self.config_change_cooldown),
+            RiyalError::ConfigChangeTooSoon
+        );
+        self.last_config_change = current_timestamp;
+        Ok(())
+    }
+
+    /// Whether any jurisdiction tag is currently blocked
+    pub fn has_blocked_regions(&self) -> bool {
+        self.blocked_regions.iter().any(|&byte| byte != 0)
+    }
+
+    /// Names and symbols must fit their reserved space, be non-empty, and contain no control or
+    /// invisible (zero-width, bidi override) characters or surrounding whitespace that could spoof UIs
+    pub fn validate_token_metadata(name: &str, symbol: &str) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= Self::MAX_TOKEN_NAME_LEN,
+            RiyalError::InvalidTokenNameLength
+        );
+        require!(
+            !symbol.is_empty() && symbol.len() <= Self::MAX_TOKEN_SYMBOL_LEN,
+            RiyalError::InvalidTokenSymbolLength
+        );
+
+        fn is_spoof_safe(text: &str) -> bool {
+            text.trim() == text
+                && !text.chars().any(|c| {
+                    c.is_control()
+                        || matches!(c, '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2064}' | '\u{2066}'..='\u{2069}' | '\u{FEFF}')
+                })
+        }
+        require!(
+            is_spoof_safe(name) && is_spoof_safe(symbol),
+            RiyalError::InvalidTokenName
+        );
+        Ok(())
+    }
+
+    /// Whether `amount` exceeds anomaly_multiplier times the running average (never before the first claim)
+    pub fn is_anomalous_claim(&self, amount: u64) -> bool {
+        self.anomaly_multiplier > 0
+            && self.claim_amount_ewma > 0
+            && amount as u128 > self.claim_amount_ewma as u128 * self.anomaly_multiplier as u128
+    }
+
+    /// Fold a claim into claim_amount_ewma; the first claim seeds the average
+    pub fn record_claim_amount(&mut self, amount: u64) {
+        self.claim_amount_ewma = if self.claim_amount_ewma == 0 {
+            amount
+        } else {
+            let avg = self.claim_amount_ewma as u128;
+            let next = (avg * (Self::ANOMALY_EWMA_DIVISOR - 1) + amount as u128) / Self::ANOMALY_EWMA_DIVISOR;
+            u64::try_from(next).unwrap_or(u64::MAX)
+        };
+    }
+
+    /// Recovery set is either disabled (no signers, threshold 0) or has 1..=signers distinct, non-default keys
+    pub fn is_valid_recovery_config(signers: &[Pubkey], threshold: u8) -> bool {
+        if signers.is_empty() {
+            return threshold == 0;
+        }
+        signers.len() <= Self::MAX_RECOVERY_SIGNERS
+            && threshold > 0
+            && threshold as usize <= signers.len()
+            && signers.iter().all(|signer| *signer != Pubkey::default())
+            && signers.iter().enumerate().all(|(i, signer)| !signers[..i].contains(signer))
+    }
+
+    /// Whether transfer_tokens is open: explicitly enabled, or a scheduled enable time has passed
+    pub fn transfers_active(&self, now: i64) -> bool {
+        self.transfers_enabled || (self.transfers_enable_at > 0 && now >= self.transfers_enable_at)
+    }
 }
 
+// Appending to TokenState changes SIZE; bump STATE_VERSION and SIZE_AT_STATE_VERSION together
+const _: () = assert!(
+    TokenState::SIZE == TokenState::SIZE_AT_STATE_VERSION,
+    "TokenState layout changed without a STATE_VERSION bump"
+);
+
 #[account]
 pub struct UserData {
     pub user: Pubkey,                     // 32 bytes
@@ -1939,6 +9210,15 @@ pub struct UserData {
     pub next_allowed_claim_time: i64,     // 8 bytes - Unix timestamp of next allowed claim
     pub total_claims: u64,                // 8 bytes - Total number of successful claims
     pub bump: u8,                         // 1 byte
+    pub region: u16,                      // 2 bytes - Jurisdiction tag (0 = untagged)
+    pub last_claim_slot: u64,             // 8 bytes - Slot of last claim
+    pub next_allowed_claim_slot: u64,     // 8 bytes - Slot of next allowed claim (slot time-lock mode)
+    pub amount_today: u64,                // 8 bytes - Tokens claimed during day_index
+    pub day_index: i64,                   // 8 bytes - Unix day (timestamp / 86400) of amount_today
+    pub range_nonce_start: u64,           // 8 bytes - nonce_start of the last range authorization used
+    pub range_consumed: u64,              // 8 bytes - Signed amount claimed against that range
+    pub last_claim_amount: u64,           // 8 bytes - Tokens minted to the user by the last claim (0 once reversed)
+    pub last_claim_daily_amount: u64,     // 8 bytes - Amount the last claim added to amount_today (0 once reversed)
 }
 
 
@@ -1949,6 +9229,187 @@ impl UserData {
         8 +                               // last_claim_timestamp
         8 +                               // next_allowed_claim_time
         8 +                               // total_claims
+        1 +                               // bump
+        2 +                               // region
+        8 +                               // last_claim_slot
+        8 +                               // next_allowed_claim_slot
+        8 +                               // amount_today
+        8 +                               // day_index
+        8 +                               // range_nonce_start
+        8 +                               // range_consumed
+        8 +                               // last_claim_amount
+        8;                                // last_claim_daily_amount
+
+    /// Read the jurisdiction tag from a user data PDA, treating an uninitialized account as untagged
+    pub fn region_of(user_data: &UncheckedAccount) -> Result<u16> {
+        if user_data.data_is_empty() {
+            return Ok(0);
+        }
+        let data = user_data.try_borrow_data()?;
+        let user_data = UserData::try_deserialize(&mut &data[..])?;
+        Ok(user_data.region)
+    }
+}
+
+/// Marker PDA (seeds: "soft_lock", owner) - while it exists the owner cannot send via program transfers
+/// Not visible to the SPL Token program: direct transfers from a thawed account bypass it
+#[account]
+pub struct SoftLock {
+    pub owner: Pubkey,                    // 32 bytes - Soft-locked token account owner
+    pub locked_at: i64,                   // 8 bytes - Unix timestamp the lock was placed
+    pub bump: u8,                         // 1 byte
+}
+
+impl SoftLock {
+    pub const SIZE: usize = 8 +           // discriminator
+        32 +                              // owner
+        8 +                               // locked_at
+        1;                                // bump
+
+    /// A soft lock is in force while its PDA holds data owned by this program
+    pub fn is_locked(soft_lock: &UncheckedAccount) -> bool {
+        soft_lock.owner == &crate::ID && !soft_lock.data_is_empty()
+    }
+}
+
+/// Marker PDA (seeds: "free_transfer", owner) - while it exists the owner's received tokens
+/// are not re-frozen by transfer_tokens during the gated phase
+#[account]
+pub struct FreeTransfer {
+    pub owner: Pubkey,                    // 32 bytes - Approved token account owner
+    pub approved_at: i64,                 // 8 bytes - Unix timestamp the approval was granted
+    pub bump: u8,                         // 1 byte
+}
+
+impl FreeTransfer {
+    pub const SIZE: usize = 8 +           // discriminator
+        32 +                              // owner
+        8 +                               // approved_at
+        1;                                // bump
+
+    /// An approval is in force while its PDA holds data owned by this program
+    pub fn is_approved(free_transfer: &UncheckedAccount) -> bool {
+        free_transfer.owner == &crate::ID && !free_transfer.data_is_empty()
+    }
+}
+
+/// Per-owner transfer counter (seeds: "transfer_stats", owner), bumped by transfer_tokens while tracking is on
+#[account]
+pub struct TransferStats {
+    pub owner: Pubkey,                    // 32 bytes - Token account owner being tracked
+    pub transfer_count: u64,              // 8 bytes - Program transfers sent while tracking was on
+    pub bump: u8,                         // 1 byte
+}
+
+impl TransferStats {
+    pub const SIZE: usize = 8 +           // discriminator
+        32 +                              // owner
+        8 +                               // transfer_count
+        1;                                // bump
+}
+
+/// Per-owner transfer approval nonce (seeds: "transfer_nonce", owner), consumed by each approved transfer_tokens
+#[account]
+pub struct TransferNonce {
+    pub owner: Pubkey,                    // 32 bytes - Sending owner
+    pub nonce: u64,                       // 8 bytes - Nonce the next admin approval must sign
+    pub bump: u8,                         // 1 byte
+}
+
+impl TransferNonce {
+    pub const SIZE: usize = 8 +           // discriminator
+        32 +                              // owner
+        8 +                               // nonce
+        1;                                // bump
+}
+
+/// Per-campaign claim totals (seeds: "source_stats", source as u16 LE), bumped by every claim tagged with `source`
+#[account]
+pub struct SourceStats {
+    pub source: u16,                      // 2 bytes - Campaign tag
+    pub claim_count: u64,                 // 8 bytes - Claims attributed to this campaign
+    pub total_amount: u64,                // 8 bytes - Tokens minted by those claims, splits included
+    pub bump: u8,                         // 1 byte
+}
+
+impl SourceStats {
+    pub const SIZE: usize = 8 +           // discriminator
+        2 +                               // source
+        8 +                               // claim_count
+        8 +                               // total_amount
+        1;                                // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdminHistoryEntry {
+    pub admin: Pubkey,                    // 32 bytes - Admin that took control
+    pub changed_at: i64,                  // 8 bytes - When control passed to this admin
+}
+
+/// Bounded ring buffer of admin custody (seeds: "admin_history")
+/// Holds the most recent MAX_ENTRIES admins; when full, the oldest entry is evicted to make room,
+/// while total_changes keeps counting so gaps in the retained history are detectable
+#[account]
+pub struct AdminHistory {
+    pub entries: Vec<AdminHistoryEntry>,  // 4 + 40 * MAX_ENTRIES bytes - Oldest first
+    pub total_changes: u64,               // 8 bytes - Entries ever recorded, including evicted ones
+    pub bump: u8,                         // 1 byte
+}
+
+impl AdminHistory {
+    /// Maximum retained entries before the oldest is evicted
+    pub const MAX_ENTRIES: usize = 16;
+
+    pub const SIZE: usize = 8 +           // discriminator
+        4 + 40 * Self::MAX_ENTRIES +      // entries
+        8 +                               // total_changes
         1;                                // bump
+
+    /// Append an admin change, evicting the oldest entry once the buffer is full
+    pub fn record(&mut self, admin: Pubkey, changed_at: i64) {
+        if self.entries.len() >= Self::MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(AdminHistoryEntry { admin, changed_at });
+        self.total_changes = self.total_changes.saturating_add(1);
+    }
+}
+
+/// Tranche lockup created by mint_tokens (seeds: "mint_lockup", beneficiary token account)
+/// The locked amount sits in the frozen "lockup_escrow" token account until released
+#[account]
+pub struct MintLockup {
+    pub beneficiary_token_account: Pubkey, // 32 bytes - Where released tranches are paid
+    pub escrow: Pubkey,                   // 32 bytes - Escrow token account holding the locked amount
+    pub total_amount: u64,                // 8 bytes - Amount minted under the lockup
+    pub released_amount: u64,             // 8 bytes - Amount already paid out
+    pub start_time: i64,                  // 8 bytes - Mint time; tranche n vests at start + n * interval
+    pub tranche_count: u16,               // 2 bytes - Number of equal tranches
+    pub tranche_interval_seconds: i64,    // 8 bytes - Time between tranches
+    pub bump: u8,                         // 1 byte
 }
 
+impl MintLockup {
+    /// Maximum tranches per lockup
+    pub const MAX_TRANCHES: u16 = 120;
+
+    pub const SIZE: usize = 8 +           // discriminator
+        32 +                              // beneficiary_token_account
+        32 +                              // escrow
+        8 +                               // total_amount
+        8 +                               // released_amount
+        8 +                               // start_time
+        2 +                               // tranche_count
+        8 +                               // tranche_interval_seconds
+        1;                                // bump
+
+    /// Amount vested at `now`; the final tranche carries any rounding remainder
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start_time).max(0);
+        let tranches = (elapsed / self.tranche_interval_seconds.max(1)).min(self.tranche_count as i64) as u64;
+        if tranches >= self.tranche_count as u64 {
+            return self.total_amount;
+        }
+        ((self.total_amount as u128 * tranches as u128) / self.tranche_count.max(1) as u128) as u64
+    }
+}
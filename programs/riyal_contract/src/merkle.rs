@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Maximum Merkle proof depth accepted on-chain (2^32 leaves)
+pub const MAX_PROOF_DEPTH: usize = 32;
+
+/// Leaf for an allowlisted claimer: sha256(0x00 | "RIYAL_ALLOWLIST" | user)
+pub fn allowlist_leaf(user: &Pubkey) -> [u8; 32] {
+    hashv(&[&[0u8], b"RIYAL_ALLOWLIST", user.as_ref()]).to_bytes()
+}
+
+/// Verify a Merkle proof built with sorted pairs: parent = sha256(0x01 | min(a, b) | max(a, b))
+// The 0x00/0x01 prefixes keep leaves and inner nodes in separate domains
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    if proof.len() > MAX_PROOF_DEPTH {
+        return false;
+    }
+
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[&[1u8], &left, &right]).to_bytes()
+    });
+
+    computed == *root
+}
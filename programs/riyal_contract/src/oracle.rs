@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use crate::errors::*;
+
+/// Anchor discriminator of the Pyth pull-oracle `PriceUpdateV2` account
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Maximum age of an oracle price accepted at claim time
+pub const MAX_ORACLE_STALENESS_SECONDS: i64 = 60;
+
+/// Maximum oracle confidence interval, in basis points of the price
+pub const MAX_ORACLE_CONFIDENCE_BPS: u128 = 200;
+
+/// Price read from an oracle account: `price * 10^exponent` USD per whole token
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Parse a fully verified Pyth `PriceUpdateV2` account
+// Layout (LE):
+//   [u8; 8]  discriminator
+//   Pubkey   write_authority
+//   enum     verification_level (0 = Partial { u8 num_signatures }, 1 = Full)
+//   [u8; 32] feed_id
+//   i64      price
+//   u64      conf
+//   i32      exponent
+//   i64      publish_time
+// Followed by: prev_publish_time, ema_price, ema_conf, posted_slot (unused)
+pub fn read_oracle_price(oracle_account: &AccountInfo) -> Result<OraclePrice> {
+    let data = oracle_account.try_borrow_data()?;
+
+    require!(
+        data.len() >= 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        RiyalError::InvalidOracleAccount
+    );
+
+    // Only accept prices with a fully verified Wormhole signature set
+    let mut offset = 8 + 32;
+    require!(
+        data.get(offset) == Some(&1),
+        RiyalError::InvalidOracleAccount
+    );
+    offset += 1;
+
+    // Skip feed_id
+    offset += 32;
+
+    fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Option<[u8; N]> {
+        data.get(offset..offset + N)?.try_into().ok()
+    }
+
+    let price = read_bytes::<8>(&data, offset).map(i64::from_le_bytes);
+    let conf = read_bytes::<8>(&data, offset + 8).map(u64::from_le_bytes);
+    let exponent = read_bytes::<4>(&data, offset + 16).map(i32::from_le_bytes);
+    let publish_time = read_bytes::<8>(&data, offset + 20).map(i64::from_le_bytes);
+
+    match (price, conf, exponent, publish_time) {
+        (Some(price), Some(conf), Some(exponent), Some(publish_time)) => Ok(OraclePrice {
+            price,
+            conf,
+            exponent,
+            publish_time,
+        }),
+        _ => err!(RiyalError::InvalidOracleAccount),
+    }
+}
+
+/// Convert a USD-cent amount into raw token units using a fresh, tight oracle price
+pub fn usd_cents_to_token_amount(
+    usd_cents: u64,
+    oracle_price: &OraclePrice,
+    decimals: u8,
+    current_timestamp: i64,
+) -> Result<u64> {
+    // Reject outdated prices
+    require!(
+        current_timestamp.saturating_sub(oracle_price.publish_time) <= MAX_ORACLE_STALENESS_SECONDS,
+        RiyalError::StaleOracle
+    );
+
+    require!(
+        oracle_price.price > 0,
+        RiyalError::InvalidOraclePrice
+    );
+    let price = oracle_price.price as u128;
+
+    // Reject prices whose confidence interval is too wide to trust
+    require!(
+        (oracle_price.conf as u128).saturating_mul(10_000) <= price.saturating_mul(MAX_ORACLE_CONFIDENCE_BPS),
+        RiyalError::OracleConfidenceTooWide
+    );
+
+    // tokens = usd_cents * 10^decimals / (price * 10^(exponent + 2))
+    let scale_exponent = oracle_price.exponent.checked_add(2).ok_or(RiyalError::InvalidOraclePrice)?;
+    let mut numerator = (usd_cents as u128)
+        .checked_mul(10u128.checked_pow(decimals as u32).ok_or(RiyalError::InvalidOraclePrice)?)
+        .ok_or(RiyalError::InvalidOraclePrice)?;
+    let mut denominator = price;
+    let scale = 10u128
+        .checked_pow(scale_exponent.unsigned_abs())
+        .ok_or(RiyalError::InvalidOraclePrice)?;
+    if scale_exponent >= 0 {
+        denominator = denominator.checked_mul(scale).ok_or(RiyalError::InvalidOraclePrice)?;
+    } else {
+        numerator = numerator.checked_mul(scale).ok_or(RiyalError::InvalidOraclePrice)?;
+    }
+
+    let token_amount = numerator / denominator;
+    u64::try_from(token_amount).map_err(|_| error!(RiyalError::InvalidOraclePrice))
+}
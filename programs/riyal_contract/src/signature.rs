@@ -5,14 +5,36 @@ use anchor_lang::solana_program::{
 };
 use crate::errors::*;
 
+/// Maximum number of instructions preceding the claim that are scanned for the Ed25519 verify
+/// This bounds verification compute regardless of how many instructions the transaction carries
+pub const MAX_SCAN_INSTRUCTIONS: u16 = 8;
+
+/// Largest signed message accepted - the text claim message, the longest format, is under 300 bytes
+pub const MAX_SIGNED_MESSAGE_SIZE: usize = 512;
+
+/// Largest Ed25519 instruction parsed: header (16) | public key (32) | signature (64) | message
+pub const MAX_ED25519_DATA_SIZE: usize = 16 + 32 + 64 + MAX_SIGNED_MESSAGE_SIZE;
+
+/// Total instruction data the non-strict scan will walk before giving up
+pub const MAX_SCANNED_DATA_SIZE: usize = MAX_SCAN_INSTRUCTIONS as usize * MAX_ED25519_DATA_SIZE;
+
 /// Verify admin Ed25519 signature only using proper Solana method with domain-separated binary messages
-/// This requires an Ed25519 verify instruction to be included BEFORE the claim instruction
+/// This requires an Ed25519 verify instruction to be included BEFORE the claim instruction,
+/// within the last MAX_SCAN_INSTRUCTIONS instructions. When `strict_position` is set, only the
+/// immediately preceding instruction is accepted.
+/// Returns the signer key that was matched, for the claim audit trail.
 pub fn verify_admin_signature_only(
     instructions_sysvar: &UncheckedAccount,
     message_bytes: &[u8],
     admin_signature: &[u8; 64],
     admin_pubkey: &Pubkey,
-) -> Result<()> {
+    strict_position: bool,
+) -> Result<Pubkey> {
+    require!(
+        message_bytes.len() <= MAX_SIGNED_MESSAGE_SIZE,
+        RiyalError::SignedMessageTooLarge
+    );
+
     let current_index = instructions::load_current_index_checked(instructions_sysvar)?;
     
     let mut admin_verified = false;
@@ -26,7 +48,7 @@ pub fn verify_admin_signature_only(
         Some(u16::from_le_bytes([bytes[0], bytes[1]]))
     }
     
-    // Parse a single-sig Ed25519 instruction created by web3.js createInstructionWithPublicKey
+    // Parse a single-sig Ed25519 instruction
     // Layout (LE):
     //   u8  numSignatures
     //   u8  padding
@@ -37,43 +59,85 @@ pub fn verify_admin_signature_only(
     //   u16 messageDataOffset
     //   u16 messageDataSize
     //   u16 messageInstructionIndex
-    // Followed by: publicKey (32) | signature (64) | message (msg_len)
-    fn parse_ed25519_single(data: &[u8]) -> Option<([u8; 32], [u8; 64], &[u8])> {
-        // Require at least 16-byte header
-        if data.len() < 16 { return None; }
+    // web3.js createInstructionWithPublicKey embeds publicKey (32) | signature (64) | message and sets
+    // every index to u16::MAX (this instruction). Other encoders reference data in another instruction
+    // of the transaction; the Ed25519 program verified exactly those referenced bytes, so they are
+    // loaded through the sysvar the same way.
+    // `expected_msg_size` rejects padded or truncated messages before any byte comparison
+    fn parse_ed25519_single(
+        instructions_sysvar: &UncheckedAccount,
+        data: &[u8],
+        expected_msg_size: usize,
+    ) -> Option<([u8; 32], [u8; 64], Vec<u8>)> {
+        const THIS_INSTRUCTION: u16 = u16::MAX;
+
+        // Require at least 16-byte header and no oversized payload
+        if data.len() < 16 || data.len() > MAX_ED25519_DATA_SIZE { return None; }
         let num_sigs = read_u8(data, 0)?;
         if num_sigs != 1 { return None; }
         let _padding = read_u8(data, 1)?;
         let sig_off = read_u16_le(data, 2)? as usize;
-        let _sig_ix = read_u16_le(data, 4)?;
+        let sig_ix = read_u16_le(data, 4)?;
         let pk_off = read_u16_le(data, 6)? as usize;
-        let _pk_ix = read_u16_le(data, 8)?;
+        let pk_ix = read_u16_le(data, 8)?;
         let msg_off = read_u16_le(data, 10)? as usize;
         let msg_size = read_u16_le(data, 12)? as usize;
-        let _msg_ix = read_u16_le(data, 14)?;
+        let msg_ix = read_u16_le(data, 14)?;
         
-        // Bounds checks
-        if pk_off.checked_add(32).filter(|&end| end <= data.len()).is_none() { return None; }
-        if sig_off.checked_add(64).filter(|&end| end <= data.len()).is_none() { return None; }
-        if msg_off.checked_add(msg_size).filter(|&end| end <= data.len()).is_none() { return None; }
+        // Cheap length check before the full message comparison
+        if msg_size != expected_msg_size { return None; }
         
-        let mut pk = [0u8; 32];
-        pk.copy_from_slice(&data[pk_off..pk_off + 32]);
-        let mut sig = [0u8; 64];
-        sig.copy_from_slice(&data[sig_off..sig_off + 64]);
-        let msg = &data[msg_off..msg_off + msg_size];
+        // Bounds-checked read of `size` bytes at `offset` in this or the referenced instruction
+        let read_ref = |index: u16, offset: usize, size: usize| -> Option<Vec<u8>> {
+            let end = offset.checked_add(size)?;
+            if index == THIS_INSTRUCTION {
+                return data.get(offset..end).map(<[u8]>::to_vec);
+            }
+            let referenced = load_instruction_at_checked(index.into(), instructions_sysvar).ok()?;
+            referenced.data.get(offset..end).map(<[u8]>::to_vec)
+        };
+        
+        let pk: [u8; 32] = read_ref(pk_ix, pk_off, 32)?.try_into().ok()?;
+        let sig: [u8; 64] = read_ref(sig_ix, sig_off, 64)?.try_into().ok()?;
+        let msg = read_ref(msg_ix, msg_off, msg_size)?;
         Some((pk, sig, msg))
     }
     
-    // Check all previous instructions for Ed25519 verifies and match against expected
-    for i in 0..current_index {
-        if let Ok(instruction) = load_instruction_at_checked(i.into(), instructions_sysvar) {
-            if instruction.program_id == ed25519_program::ID {
-                if let Some((pk, sig, msg)) = parse_ed25519_single(&instruction.data) {
-                    // Require exact message match
-                    if msg == message_bytes {
-                        if !admin_verified && pk.as_ref() == admin_pubkey.as_ref() && sig.as_ref() == admin_signature {
-                            admin_verified = true;
+    if strict_position {
+        // Strict mode: the Ed25519 verify must be the immediately preceding instruction
+        require!(
+            current_index > 0,
+            RiyalError::InvalidEd25519Instruction
+        );
+        let instruction = load_instruction_at_checked((current_index - 1).into(), instructions_sysvar)
+            .map_err(|_| RiyalError::InvalidEd25519Instruction)?;
+        let matched = instruction.program_id == ed25519_program::ID
+            && parse_ed25519_single(instructions_sysvar, &instruction.data, message_bytes.len()).is_some_and(|(pk, sig, msg)| {
+                msg == message_bytes && pk.as_ref() == admin_pubkey.as_ref() && sig.as_ref() == admin_signature
+            });
+        require!(
+            matched,
+            RiyalError::InvalidEd25519Instruction
+        );
+        admin_verified = true;
+    } else {
+        // Check only the most recent previous instructions for Ed25519 verifies and match against expected
+        let scan_start = current_index.saturating_sub(MAX_SCAN_INSTRUCTIONS);
+        let mut scanned_data_size = 0usize;
+        for i in scan_start..current_index {
+            if let Ok(instruction) = load_instruction_at_checked(i.into(), instructions_sysvar) {
+                // Sanity cap on the total data walked, however the preceding instructions are padded
+                scanned_data_size = scanned_data_size.saturating_add(instruction.data.len());
+                if scanned_data_size > MAX_SCANNED_DATA_SIZE {
+                    break;
+                }
+                if instruction.program_id == ed25519_program::ID {
+                    if let Some((pk, sig, msg)) = parse_ed25519_single(instructions_sysvar, &instruction.data, message_bytes.len()) {
+                        // Require exact message match
+                        if msg == message_bytes {
+                            if !admin_verified && pk.as_ref() == admin_pubkey.as_ref() && sig.as_ref() == admin_signature {
+                                admin_verified = true;
+                            }
                         }
                     }
                 }
@@ -87,9 +151,72 @@ pub fn verify_admin_signature_only(
         RiyalError::AdminSignatureNotVerified
     );
     
-    msg!(
-        "REAL ED25519 VERIFICATION SUCCESS: Admin signature cryptographically verified"
+    Ok(*admin_pubkey)
+}
+/// Count the distinct `signers` that signed `message_bytes` in Ed25519 verify instructions
+/// among the last MAX_SCAN_INSTRUCTIONS instructions before this one
+/// Each verify instruction may carry several signatures, but every offset must point into the
+/// verify instruction itself (instruction index u16::MAX), so the checked bytes are the parsed ones
+pub fn count_threshold_signers(
+    instructions_sysvar: &UncheckedAccount,
+    message_bytes: &[u8],
+    signers: &[Pubkey],
+) -> Result<usize> {
+    require!(
+        message_bytes.len() <= MAX_SIGNED_MESSAGE_SIZE,
+        RiyalError::SignedMessageTooLarge
     );
-    
-    Ok(())
-}
\ No newline at end of file
+
+    // Per-signature offsets (LE): sig offset, sig ix, pubkey offset, pubkey ix, msg offset, msg size, msg ix
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+    const THIS_INSTRUCTION: u16 = u16::MAX;
+
+    fn read_u16_le(data: &[u8], offset: usize) -> Option<u16> {
+        data.get(offset..offset + 2).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    let current_index = instructions::load_current_index_checked(instructions_sysvar)?;
+    let scan_start = current_index.saturating_sub(MAX_SCAN_INSTRUCTIONS);
+    let mut matched = vec![false; signers.len()];
+
+    for i in scan_start..current_index {
+        let Ok(instruction) = load_instruction_at_checked(i.into(), instructions_sysvar) else {
+            continue;
+        };
+        if instruction.program_id != ed25519_program::ID {
+            continue;
+        }
+        let data = &instruction.data;
+        let num_sigs = data.first().copied().unwrap_or(0) as usize;
+
+        for sig_index in 0..num_sigs {
+            let base = OFFSETS_START + sig_index * OFFSETS_SIZE;
+            let fields: Option<Vec<u16>> = (0..7).map(|field| read_u16_le(data, base + field * 2)).collect();
+            let Some(fields) = fields else {
+                break;
+            };
+            let (sig_ix, pk_off, pk_ix, msg_off, msg_size, msg_ix) =
+                (fields[1], fields[2] as usize, fields[3], fields[4] as usize, fields[5] as usize, fields[6]);
+
+            if sig_ix != THIS_INSTRUCTION || pk_ix != THIS_INSTRUCTION || msg_ix != THIS_INSTRUCTION {
+                continue;
+            }
+            // Cheap length check before the full message comparison
+            if msg_size != message_bytes.len() {
+                continue;
+            }
+            let (Some(pk), Some(msg)) = (data.get(pk_off..pk_off + 32), data.get(msg_off..msg_off + msg_size)) else {
+                continue;
+            };
+            if msg != message_bytes {
+                continue;
+            }
+            if let Some(position) = signers.iter().position(|signer| signer.as_ref() == pk) {
+                matched[position] = true;
+            }
+        }
+    }
+
+    Ok(matched.iter().filter(|&&is_matched| is_matched).count())
+}